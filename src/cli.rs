@@ -25,7 +25,10 @@ pub struct Cli {
 
 impl Cli {
     /// Create a new scanner interface for the following PID
-    pub fn new(pid: Pid, prompt: String) -> crate::Result<Self> {
+    ///
+    /// `read_budget`, if given, overrides the scanner's default per-scan
+    /// read budget (see [`Scanner::with_read_budget`]).
+    pub fn new(pid: Pid, prompt: String, read_budget: Option<usize>) -> crate::Result<Self> {
         // Make sure we can read from this process
         let _ = crate::Maps::accessible(pid)?;
 
@@ -50,7 +53,10 @@ impl Cli {
         // println!("{commands:?}");
 
         // Create the scanner
-        let scanner = Scanner::new(pid);
+        let scanner = match read_budget {
+            Some(bytes) => Scanner::new(pid).with_read_budget(bytes),
+            None => Scanner::new(pid),
+        };
 
         Ok(Self { rl, history_file, prompt, commands, scanner })
     }
@@ -83,6 +89,10 @@ impl Cli {
                     Ok(_) => (),
                     Err(e)  => println!("!!! {e}"),
                 }
+
+                // The memory snapshot is only valid for the command that
+                // captured it; drop it so the next one starts fresh
+                self.scanner.invalidate_snapshot();
             } else {
                 println!("Unknown command!");
             }
@@ -31,7 +31,7 @@ fn handler(s: &mut crate::Scanner, _args: &[&str]) -> crate::commands::Result {
     // Any remaining elements in `last` are unique
     diff.extend_from_slice(&last[i..]);
 
-    crate::commands::print_results(s.pid(), &diff, usize::MAX);
+    crate::commands::print_results(s.pid(), &diff, usize::MAX)?;
 
     Ok(())
 }
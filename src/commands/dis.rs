@@ -0,0 +1,42 @@
+use crate::commands::parse_arg;
+use crate::disasm::DisasmError;
+
+crate::register_command_handler!(
+    handler, ["dis", "disassemble"],
+    "Disassemble x86-64 instructions at an address",
+r#"`<address> <count>`
+* `address` - The address to start disassembling from. Must fall within an
+  executable memory region.
+* `count` - How many instructions to decode.
+"#
+);
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    let addr  = parse_arg::<u64>(args.get(1), "Address")?;
+    let count = parse_arg::<usize>(args.get(2), "Count")?;
+
+    let maps = crate::Maps::r_regions(s.pid())
+        .map_err(|e| format!("Couldn't parse memory map: {:?}", e))?;
+    let executable = maps.0.iter().any(|reg|
+        reg.addr.contains(&addr) && reg.perms.execute);
+    if !executable {
+        return Err(format!("0x{addr:x} is not in an executable region"));
+    }
+
+    let instructions = crate::disasm::decode(s.pid(), addr, count)
+        .map_err(|e| match e {
+            DisasmError::ReadFailed => "Couldn't read target memory".to_string(),
+            DisasmError::InvalidInstruction(byte) =>
+                format!("Invalid instruction (byte 0x{byte:02x})"),
+        })?;
+
+    for insn in instructions {
+        let bytes: String = insn.bytes.iter()
+            .map(|b| format!("{b:02x}"))
+            .intersperse(" ".to_string())
+            .collect();
+        println!("0x{:x}: {:<32} {} {}", insn.addr, bytes, insn.mnemonic, insn.operands);
+    }
+
+    Ok(())
+}
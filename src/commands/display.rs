@@ -34,8 +34,14 @@ fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
     // Make sure we have nonzero length
     let len = NonZero::new(len).ok_or("Length must not be zero!")?;
 
-    // Read the memory
-    let mem = remote::read(s.pid(), addr, len)
+    // Read the memory, resolving against the cached snapshot first and
+    // falling back to a direct read of the whole range if the snapshot
+    // doesn't cover all of it
+    let mem = s.snapshot()
+        .map_err(|e| format!("Couldn't snapshot memory: {:?}", e))?
+        .slice(addr, len.get())
+        .map(<[u8]>::to_vec)
+        .or_else(|| remote::read(s.pid(), addr, len))
         .ok_or(format!("Couldn't read remote memory at 0x{:X?}", addr))?;
 
     // Derived constants:
@@ -91,10 +97,10 @@ fn print_value(s: &crate::Scanner, chunk: &[u8], mut value: crate::num::Value) {
     if chunk.len() == value.bytes() {
         // Full chunk: convert the bytes into the requested value type
         value.from_le_bytes(chunk);
-        // Check whether the value is a valid readable pointer. If it is, we
-        // colorize it
-        let len = NonZero::new(1).unwrap();
-        let is_valid = remote::read(s.pid(), value.as_u64(), len).is_some();
+        // Check whether the value is a valid readable pointer against the
+        // cached snapshot, rather than issuing a read for every value
+        let is_valid = s.snapshot.as_ref()
+            .is_some_and(|snap| snap.is_readable(value.as_u64()));
 
         if is_valid {
             print!("\x1b[0;32m{value}\x1b[0m ");
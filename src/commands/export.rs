@@ -0,0 +1,29 @@
+use crate::ExportFormat;
+
+crate::register_command_handler!(
+    handler, ["export"],
+    "Export the full scan history to a file as JSON or CSV",
+r#"`<format> <path>`
+* `format` - `json` or `csv`
+* `path` - File to write the export to. Overwritten if it already exists.
+"#
+);
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    let format = match args.get(1).copied() {
+        Some("json") => ExportFormat::Json,
+        Some("csv")  => ExportFormat::Csv,
+        _ => return Err("Format must be 'json' or 'csv'".to_string()),
+    };
+
+    let path = args.get(2).ok_or("Path missing".to_string())?;
+
+    let file = std::fs::File::create(path)
+        .map_err(|e| format!("Couldn't create '{}': {:?}", path, e))?;
+
+    s.export(format, file)
+        .map_err(|e| format!("Couldn't write export: {:?}", e))?;
+
+    println!("Exported scan history to '{}'", path);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::remote;
+use crate::commands::write::parse_write_value;
+
+crate::register_command_handler!(
+    handler, ["freeze"],
+    "Continuously re-write a value to every address in the current scan results.",
+r#"`<type> <value> [<interval_ms>]`
+* `type` - Value type letter (see `db`/`dw`/... for the list)
+* `value` - The value to keep writing, parsed according to `type`
+* `interval_ms` - How often to re-write the value, in milliseconds. 100 by
+  default.
+"#
+);
+
+/// Default interval between writes, in milliseconds
+const DEFAULT_INTERVAL_MS: u64 = 100;
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    // Parse the value type and the value itself
+    let value = parse_write_value(args)?;
+
+    // Parse the re-write interval
+    let interval = args.get(3)
+        .map(|s| crate::num::parse::<u64>(s)
+            .map_err(|e| format!("Interval not a valid number: {:?}", e)))
+        .transpose()?
+        .unwrap_or(DEFAULT_INTERVAL_MS);
+
+    // Grab the addresses to keep overwriting
+    let addrs: Vec<u64> = s.results.last()
+        .filter(|r| !r.is_empty())
+        .ok_or("No scan results to freeze".to_string())?
+        .iter()
+        .map(|&(addr, _)| addr)
+        .collect();
+
+    let pid = s.pid();
+    let bytes = value.to_le_bytes();
+    let interval = std::time::Duration::from_millis(interval);
+    let count = addrs.len();
+
+    // Spawn a background thread that keeps re-writing the value in batches of
+    // vectored syscalls, rather than one syscall per address per tick
+    std::thread::spawn(move || loop {
+        let writes: Vec<(u64, &[u8])> = addrs.iter().map(|&addr| (addr, bytes.as_slice())).collect();
+        let _ = remote::write_vecs(pid, &writes);
+        std::thread::sleep(interval);
+    });
+
+    println!("Freezing {count} addresses");
+
+    Ok(())
+}
@@ -24,7 +24,7 @@ fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
 
     // Show the addresses
     if let Some(results) = s.results.get(idx) {
-        crate::commands::print_results(s.pid(), results, n_show);
+        crate::commands::print_results(s.pid(), results, n_show)?;
     }
 
     Ok(())
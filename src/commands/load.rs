@@ -0,0 +1,57 @@
+use crate::Modules;
+use crate::commands::pair_with_fresh_values;
+
+crate::register_command_handler!(
+    handler, ["load"],
+    "Load results previously written by `save`, re-resolving them against the \
+     current memory map",
+r#"`<path>`
+* `path` - File previously written by `save`.
+
+Module-relative entries (`name+0xoffset`) are re-resolved against a fresh read
+of the process's modules, so results survive the process having restarted at a
+different base address. Entries whose module is no longer mapped are skipped.
+"#
+);
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    let path = args.get(1).ok_or("Path missing".to_string())?;
+
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Couldn't read '{}': {:?}", path, e))?;
+
+    let modules = Modules::new(s.pid())
+        .map_err(|e| format!("Couldn't parse memory map: {:?}", e))?;
+
+    let mut addrs = Vec::new();
+    let mut skipped = 0;
+
+    for line in contents.lines().filter(|l| !l.is_empty()) {
+        match parse_entry(line, &modules) {
+            Some(addr) => addrs.push(addr),
+            None => skipped += 1,
+        }
+    }
+
+    if skipped > 0 {
+        println!("Skipped {skipped} entries that no longer resolve");
+    }
+
+    // No value was ever recorded for a loaded address, so read whatever is
+    // there now
+    let results = pair_with_fresh_values(s.pid(), addrs);
+    crate::commands::print_and_save_results(s, results)
+}
+
+/// Resolves a single `save`d entry (`name+0xoffset` or `0xaddress`) against
+/// `modules`, returning `None` if its module is no longer mapped
+fn parse_entry(entry: &str, modules: &Modules) -> Option<u64> {
+    match entry.split_once('+') {
+        Some((name, offset)) => {
+            let module = modules.by_name(name)?;
+            let offset = u64::from_str_radix(offset.trim_start_matches("0x"), 16).ok()?;
+            Some(module.base + offset)
+        }
+        None => u64::from_str_radix(entry.trim_start_matches("0x"), 16).ok(),
+    }
+}
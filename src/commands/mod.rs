@@ -11,6 +11,8 @@
 use std::collections::HashMap;
 use crate::Scanner;
 
+mod utils;
+pub use utils::*;
 
 // COMMAND REGISTRATION ────────────────────────────────────────────────────────
 // Things are imported using this macro to automatically expose command
@@ -25,14 +27,37 @@ macro_rules! import_command {
 
 import_command!(exit);
 import_command!(maps);
+import_command!(write);
+import_command!(freeze);
+import_command!(pattern_scan);
+import_command!(string_scan);
+import_command!(save);
+import_command!(load);
+import_command!(display);
+import_command!(patch);
+import_command!(scan);
+import_command!(rescan);
+import_command!(refine);
+import_command!(export);
+
+#[cfg(feature = "disasm")]
+import_command!(dis);
+#[cfg(feature = "disasm")]
+import_command!(sig);
 
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Result type returned by command handlers
+///
+/// The `Err` variant carries a human-readable message that the CLI prints
+/// back to the user; it is not meant to be matched on.
+pub type Result = std::result::Result<(), String>;
+
 /// Command handler type
 ///
-/// A command will be given the scanner state and a list of arguments that it
-/// can then handle.
-pub type CommandHandler = fn(&mut Scanner, &[String]) -> String;
+/// A command will be given the scanner state and a list of arguments
+/// (including the command word itself as `args[0]`) that it can then handle.
+pub type CommandHandler = fn(&mut Scanner, &[&str]) -> Result;
 
 /// A single command handler registration entry
 ///
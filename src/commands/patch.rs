@@ -0,0 +1,50 @@
+use crate::remote;
+use crate::commands::{parse_arg, parse_value};
+
+crate::register_command_handler!(
+    handler, ["wb", "ww", "wd", "wq", "wB", "wW", "wD", "wQ", "wf", "wF"],
+    "Write one or more consecutive memory values.",
+r#"`<address> <value> [<value>...]`
+* `address` - The address to start writing at, in decimal or as hex with a
+  `0x` prefix.
+* `value` - One or more values to write, parsed according to the command's
+  type letter (see `db`/`dw`/... for the list). Values are written
+  back-to-back starting at `address` in a single vectored syscall.
+"#
+);
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    // Parse the value type from the command letter
+    let mut value = parse_value(args.get(0))?;
+
+    // Parse the starting address
+    let addr = parse_arg::<u64>(args.get(1), "Address")?;
+
+    // Parse every value into little-endian bytes
+    let val_strs = args.get(2..).filter(|v| !v.is_empty())
+        .ok_or("Value(s) missing".to_string())?;
+
+    let mut encoded = Vec::with_capacity(val_strs.len());
+    for val_str in val_strs {
+        value.update_str(val_str)
+            .map_err(|e| format!("Invalid value '{}': {:?}", val_str, e))?;
+        encoded.push(value.to_le_bytes());
+    }
+
+    // Lay the encoded values out back-to-back starting at `addr`
+    let mut cursor = addr;
+    let writes: Vec<(u64, &[u8])> = encoded.iter()
+        .map(|bytes| {
+            let pair = (cursor, bytes.as_slice());
+            cursor += bytes.len() as u64;
+            pair
+        })
+        .collect();
+
+    // Write them all in a single vectored syscall
+    let results = remote::write_vecs(s.pid(), &writes);
+    let written = results.iter().filter(|&&ok| ok).count();
+
+    println!("Wrote {written}/{} value(s)", writes.len());
+    Ok(())
+}
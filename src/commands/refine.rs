@@ -0,0 +1,25 @@
+use crate::commands::parse_refine;
+
+crate::register_command_handler!(
+    handler, ["refine"],
+    "Narrow the last history entry by comparing a fresh read to its recorded value.",
+r#"`<predicate> [<amount>]`
+* `predicate` - `changed`, `unchanged`, `increased`, `decreased`,
+  `increased-by <amount>`, or `decreased-by <amount>`.
+
+Unlike `u*`, this works against any history entry, not just ones from an
+`s*` scan, since it compares raw values instead of a typed one.
+"#
+);
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    let predicate = parse_refine(args.get(1..).unwrap_or(&[]))?;
+
+    let before = s.results.last().map(Vec::len).unwrap_or(0);
+    s.refine(predicate);
+
+    let after = s.results.last().map(Vec::len).unwrap_or(0);
+    println!("{after} of {before} result(s) survived");
+
+    Ok(())
+}
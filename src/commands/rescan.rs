@@ -1,35 +1,49 @@
-use crate::commands::{parse_value, parse_constraints, scan_batch};
-use crate::{CHUNK_SIZE, remote::IoVec};
+use crate::commands::{parse_value, parse_rescan_constraint};
+use crate::remote::IoVec;
 
 crate::register_command_handler!(
     handler, ["ub", "uw", "ud", "uq", "uB", "uW", "uD", "uQ", "uf", "uF"],
-    "Rescan the resutls from previous scan for new values.",
-r#"`<constraints>`
-* `constraints` - The constraints by which to scan
+    "Rescan the results from the previous scan against their prior values.",
+r#"`<predicate> [<amount>]`
+* `predicate` - `changed`, `unchanged`, `increased`, `decreased`,
+  `increased-by <amount>`, or `decreased-by <amount>`, checked against the
+  value recorded by the last `s*`/`u*` scan.
 "#
 );
 
 fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
-    // Parse the value type and the constraints
+    // Parse the value type and the rescan predicate
     let value = parse_value(args.get(0))?;
+    let predicate = parse_rescan_constraint(args.get(1..).unwrap_or(&[]), value)?;
 
-    // Parse the constraints
-    let constraints = parse_constraints(&args[1..], value)?;
+    // There must be a match set from a previous scan to narrow
+    let previous = s.value_matches.take()
+        .filter(|matches| !matches.is_empty())
+        .ok_or("No previous scan results to rescan".to_string())?;
 
-    // Create iovecs for the addresses returned by the previous scan
+    // Read only the previously matched addresses
     let bytes = core::num::NonZero::new(value.bytes()).unwrap();
-    let iovecs: Vec<IoVec> = s.results.iter()
-        .map(|&addr| IoVec::new(addr, bytes))
+    let iovecs: Vec<IoVec> = previous.iter()
+        .map(|&(addr, _)| IoVec::new(addr, bytes))
         .collect();
+    let memory = crate::remote::read_vecs(s.pid(), &iovecs);
 
-    // Search for the values and save off the adresses where they're found
-    let mut matches = Vec::new();
+    // Keep only the addresses that are still readable and satisfy the
+    // predicate relative to their previously recorded value
+    let mut survivors = Vec::new();
+    for (&(addr, previous_value), mem) in previous.iter().zip(memory.into_iter()) {
+        if mem.len() != value.bytes() { continue; }
 
-    for batch in iovecs.chunks(CHUNK_SIZE / value.bytes()) {
-        scan_batch(s.pid(), &mut matches, batch, value, &constraints);
-    }
+        let mut current = value;
+        current.from_le_bytes(&mem);
 
-    crate::commands::print_and_save_results(s, matches);
+        if predicate.check(previous_value, current) {
+            survivors.push((addr, current));
+        }
+    }
 
-    Ok(())
+    // Collapse the match set in place and report the surviving addresses
+    let addrs = survivors.iter().map(|&(addr, v)| (addr, v.as_u64())).collect();
+    s.value_matches = Some(survivors);
+    crate::commands::print_and_save_results(s, addrs)
 }
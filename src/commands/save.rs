@@ -0,0 +1,39 @@
+use crate::Modules;
+
+crate::register_command_handler!(
+    handler, ["save"],
+    "Save the current scan results to a file as module-relative addresses",
+r#"`<path>`
+* `path` - File to write the results to. Overwritten if it already exists.
+
+Addresses backed by a known module are stored as `name+0xoffset`, so they can
+be re-resolved with `load` even if the process restarts and gets placed at a
+different base address. Addresses outside of any module are stored as raw
+`0xaddress` and re-read verbatim on `load`.
+"#
+);
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    let path = args.get(1).ok_or("Path missing".to_string())?;
+
+    let results = s.results.last()
+        .filter(|r| !r.is_empty())
+        .ok_or("No scan results to save".to_string())?;
+
+    let modules = Modules::new(s.pid())
+        .map_err(|e| format!("Couldn't parse memory map: {:?}", e))?;
+
+    let contents: String = results.iter()
+        .map(|&(addr, _)| match modules.find(addr) {
+            Some(module) => format!("{}+0x{:x}", module.name, addr - module.base),
+            None => format!("0x{addr:x}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    std::fs::write(path, contents)
+        .map_err(|e| format!("Couldn't write '{}': {:?}", path, e))?;
+
+    println!("Saved {} addresses to '{}'", results.len(), path);
+    Ok(())
+}
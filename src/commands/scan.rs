@@ -3,18 +3,23 @@ use crate::commands::{parse_arg, parse_value, parse_constraints};
 crate::register_command_handler!(
     handler, ["sb", "sw", "sd", "sq", "sB", "sW", "sD", "sQ", "sf", "sF"],
     "Scan the memory for values.",
-r#"`<start_address> <end_address> <constraints>`
+r#"`<start_address> <end_address> [<constraint>] [--heap|--stack|--anon|--file]`
 * `start_address` - Start scanning from this address. If this is `0`, the
    scan will start from the first readable memory region.
 * `end_address` - Stop scanning at this address. If this is `0`, the scan
    will stop at the last readable memory region.
-* `constraints` - The constraints by which to scan
+* `constraint` - `== <v>`, `!= <v>`, `> <v>`, `< <v>`, a range `<a>..<b>`, or
+  nothing at all / `unknown` to keep every aligned slot and record its
+  current value for later rescans with `u*`.
+* A trailing `--heap`/`--stack`/`--anon`/`--file` restricts the scan to
+  regions backed by that kind instead of every readable region.
 "#
 );
 
 fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
     // Parse the value type
-    let mut value = parse_value(args.get(0))?;
+    let value = parse_value(args.get(0))?;
+    let letter = args[0].chars().nth(1).unwrap();
 
     // Parse the start and end addresses
     let start = parse_arg::<u64>(args.get(1), "Start address")?;
@@ -23,45 +28,29 @@ fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
     // If end is undefined, default to the maximum address
     let end = if end == 0 { u64::MAX } else { end };
 
-    // Parse the constraints
-    let constraints = parse_constraints(&args[3..], value)?;
-
-    // Get the memory map
-    let maps = crate::proc_maps::Maps::rw_regions(s.pid())
-        .map_err(|e| format!("Couldn't parse memory map: {:?}", e))?;
-
-    // Get the iovec batches
-    let iovecs = maps.chunks(core::ops::Range { start, end });
-
-    // Search for the values and save off the adresses where they're found
-    let mut matches = Vec::new();
-
-    for batch in iovecs.into_iter() {
-        // Read the memory
-        let memory = crate::remote::read_vecs(s.pid(), &batch);
-
-        // Retain only those chunks of memory that have been successfully read
-        let chunks = batch.iter().zip(memory.into_iter())
-            .filter(|(_, mem)| mem.is_some())
-            .map(|(iovec, mem)| (iovec, mem.unwrap()));
-
-        // Go through each region and scan for the value
-        for (iovec, mem) in chunks {
-            // Go through the region in chunks
-            for (offset, chunk) in mem.chunks_exact(value.bytes()).enumerate() {
-                // Update the value
-                value.from_le_bytes(chunk);
-
-                // Check that constraints match and if they do, save the address
-                if constraints.iter().all(|x| x.check(value)) {
-                    let abs = iovec.base + offset as u64 * value.bytes() as u64;
-                    matches.push(abs);
-                }
-            }
+    // A trailing `--<kind>` token restricts which regions get scanned;
+    // everything before it (if anything) is the constraint
+    let rest = args.get(3..).unwrap_or(&[]);
+    let (kind, constraint_args) = match rest.last() {
+        Some(tok) if tok.starts_with("--") => {
+            let kind = crate::proc_maps::RegionKind::from_flag(tok)
+                .ok_or_else(|| format!("Unknown region filter '{}'", tok))?;
+            (Some(kind), &rest[..rest.len() - 1])
         }
-    }
-
-    crate::commands::print_and_save_results(s, matches);
-
-    Ok(())
+        _ => (None, rest),
+    };
+
+    // Parse the constraint here too, so bad syntax is rejected before we
+    // bother spawning a worker for it
+    parse_constraints(constraint_args, value)?;
+
+    // The actual scan runs in a re-exec'd worker subprocess, so a fault or
+    // wedge in process_vm_readv takes down the worker instead of us
+    let matches = crate::worker::supervised_scan(
+        s.pid(), letter, start, end, constraint_args, kind, value, s.read_budget())?;
+
+    // Save the snapshot for rescanning and report the addresses
+    let addrs = matches.iter().map(|&(addr, v)| (addr, v.as_u64())).collect();
+    s.value_matches = Some(matches);
+    crate::commands::print_and_save_results(s, addrs)
 }
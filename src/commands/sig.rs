@@ -0,0 +1,140 @@
+use crate::commands::{parse_arg, scan_contiguous};
+use crate::pattern::Pattern;
+
+crate::register_command_handler!(
+    handler, ["sig", "makesig"],
+r#"Generate a minimal IDA byte signature that uniquely identifies an address
+
+Decodes one instruction at a time starting at the given address, masking out
+bytes that change across recompiles or ASLR (RIP-relative displacements,
+absolute addresses and pointer-sized immediates) and keeping the rest. After
+each instruction, the accumulated pattern is scanned across all executable
+memory; generation stops as soon as the pattern matches exactly once."#,
+r#"`<address>`
+* `address` - The code address to generate a signature for.
+"#
+);
+
+/// Stop generating once the accumulated pattern exceeds this many
+/// instructions or bytes, whichever comes first
+const MAX_INSTRUCTIONS: usize = 32;
+const MAX_BYTES: usize = 128;
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    let addr = parse_arg::<u64>(args.get(1), "Address")?;
+
+    let maps = crate::Maps::regions(s.pid(), |reg| reg.perms.read && reg.perms.execute)
+        .map_err(|e| format!("Couldn't parse memory map: {:?}", e))?;
+
+    let mut tokens: Vec<String> = Vec::new();
+    let mut unique = false;
+
+    for count in 1..=MAX_INSTRUCTIONS {
+        let instructions = crate::disasm::decode(s.pid(), addr, count)
+            .map_err(|e| format!("Couldn't decode instructions: {:?}", e))?;
+
+        if instructions.is_empty() {
+            return Err("No instructions decoded at that address".to_string());
+        }
+
+        tokens = tokens_for(&instructions);
+        if tokens.len() > MAX_BYTES {
+            break;
+        }
+
+        let pattern_str: Vec<&str> = tokens.iter().map(String::as_str).collect();
+        let Ok(pattern) = Pattern::parse_scored_anchors(Some(&pattern_str)) else {
+            // No stable bytes yet (every instruction so far was fully
+            // volatile) - keep decoding until something stable shows up
+            continue;
+        };
+
+        let pattern_len = pattern.pattern_len();
+        let iovecs = maps.clone().chunks(core::ops::Range { start: 0, end: u64::MAX }, s.read_budget());
+        let matches = scan_contiguous(s.pid(), iovecs, pattern_len, |mem| {
+            pattern.find_pattern_iter(mem).map(|off| (off, pattern_len)).collect()
+        });
+
+        if matches.len() == 1 {
+            unique = true;
+            break;
+        }
+    }
+
+    // Trim trailing wildcard bytes (including whole wildcard-only
+    // instructions): they don't constrain the match and there's no point
+    // keeping them in the final signature
+    while tokens.last().is_some_and(|t| t == "??") {
+        tokens.pop();
+    }
+
+    let pattern = tokens.join(" ");
+    if unique {
+        println!("Unique signature: {pattern}");
+    } else {
+        println!("Warning: could not find a unique signature within the instruction/byte cap");
+        println!("Best effort: {pattern}");
+    }
+
+    Ok(())
+}
+
+/// Decodes each instruction's raw bytes into IDA pattern tokens (hex bytes or
+/// `??`), masking out volatile byte ranges: RIP-relative displacements and
+/// pointer-sized (64-bit) immediates, wherever they actually fall in the
+/// encoding
+fn tokens_for(instructions: &[crate::disasm::Instruction]) -> Vec<String> {
+    let mut tokens = Vec::new();
+
+    for insn in instructions {
+        let volatile = volatile_mask(insn);
+
+        for (i, &byte) in insn.bytes.iter().enumerate() {
+            if volatile[i] {
+                tokens.push("??".to_string());
+            } else {
+                tokens.push(format!("{byte:02X}"));
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Marks which bytes of this instruction's encoding are volatile (liable to
+/// change across recompiles or ASLR): a RIP-relative displacement and/or a
+/// pointer-sized (64-bit) immediate.
+///
+/// A displacement doesn't have to trail an instruction's immediate (e.g.
+/// `cmp dword ptr [rip+X], imm32` encodes the displacement before the
+/// immediate), so the volatile ranges are located with the decoder's own
+/// constant offsets (`Decoder::get_constant_offsets`) rather than assumed to
+/// be the instruction's trailing bytes.
+fn volatile_mask(insn: &crate::disasm::Instruction) -> Vec<bool> {
+    use iced_x86::{Decoder, DecoderOptions};
+
+    let mut decoder = Decoder::with_ip(64, &insn.bytes, insn.addr, DecoderOptions::NONE);
+    let decoded = decoder.decode();
+    let offsets = decoder.get_constant_offsets(&decoded);
+
+    let mut mask = vec![false; insn.bytes.len()];
+    let mut mark = |offset: u32, size: u32| {
+        let (offset, size) = (offset as usize, size as usize);
+        if let Some(range) = mask.get_mut(offset..offset + size) {
+            range.fill(true);
+        }
+    };
+
+    if decoded.is_ip_rel_memory_operand() && offsets.has_displacement() {
+        mark(offsets.displacement_offset() as u32, offsets.displacement_size() as u32);
+    }
+
+    if offsets.has_immediate() && offsets.immediate_size() as u32 == 8 {
+        mark(offsets.immediate_offset() as u32, 8);
+    }
+    if offsets.has_immediate2() && offsets.immediate_size2() as u32 == 8 {
+        mark(offsets.immediate_offset2() as u32, 8);
+    }
+
+    mask
+}
@@ -1,9 +1,9 @@
 use memchr::memmem;
-use crate::commands::parse_arg;
+use crate::commands::{parse_arg, scan_contiguous, pair_with_fresh_values};
 
 crate::register_command_handler!(
-    handler, ["ss", "ss16", "ss32"],
-    "Search for a string (or a UTF-16 or UTF-32 wide string)",
+    handler, ["ss", "str", "string"],
+    "Search for a string, trying ASCII, UTF-8 and UTF-16LE encodings at once",
 r#"`<start_address> <end_address> <string>`
 * `start_address` - Start searching from this address. If this is `0`, the
    search will start from the first readable memory region.
@@ -27,58 +27,51 @@ fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
         .map(|parts| parts.join(" "))
         .ok_or("String missing!")?;
 
-    // Encode the string depending on the command we're handling
-    let cmd = args[0];
-    let needle = if cmd.ends_with("16") {
-        let mut buf = Vec::with_capacity(string.len() * 2);
-        for unit in string.encode_utf16() {
-            buf.push((unit & 0xFF) as u8);
-            buf.push((unit >> 8) as u8);
-        }
-        buf
-    } else if cmd.ends_with("32") {
-        let mut buf = Vec::with_capacity(string.len() * 4);
-        for ch in string.chars() {
-            let val = ch as u32;
-            buf.push((val & 0xFF) as u8);
-            buf.push(((val >>  8) & 0xFF) as u8);
-            buf.push(((val >> 16) & 0xFF) as u8);
-            buf.push(((val >> 24) & 0xFF) as u8);
-        }
-        buf
-    } else {
-        string.as_bytes().to_vec()
-    };
+    // Build the candidate byte patterns: ASCII (when representable), UTF-8
+    // (Rust's native string representation) and UTF-16LE
+    let needles = encode_candidates(&string);
 
     // Get the memory map
     let maps = crate::proc_maps::Maps::interesting_regions(s.pid())
         .map_err(|e| format!("Couldn't parse memory map: {:?}", e))?;
 
     // Get the iovec batches
-    let iovecs = maps.chunks(core::ops::Range { start, end });
+    let iovecs = maps.chunks(core::ops::Range { start, end }, s.read_budget());
 
-    // Search for the string and save off the adresses where it's found
-    let mut matches = Vec::new();
+    // Carry enough bytes across reads to catch the longest candidate
+    // straddling a chunk boundary
+    let max_len = needles.iter().map(Vec::len).max().unwrap_or(0);
 
-    for batch in iovecs.into_iter() {
-        // Read the memory
-        let memory = crate::remote::read_vecs(s.pid(), &batch);
+    // Search for every candidate encoding and save off the adresses where any
+    // of them are found
+    let matches = scan_contiguous(s.pid(), iovecs, max_len, |mem| {
+        needles.iter()
+            .flat_map(|needle| memmem::find_iter(mem, needle)
+                .map(|off| (off, needle.len())))
+            .collect()
+    });
 
-        // Retain only those chunks of memory that have been successfully read
-        let chunks = batch.iter().zip(memory.into_iter())
-            .filter(|(_, mem)| mem.is_some())
-            .map(|(iovec, mem)| (iovec, mem.unwrap()));
+    let results = pair_with_fresh_values(s.pid(), matches);
+    crate::commands::print_and_save_results(s, results)
+}
 
-        // Go through each region and scan for the string
-        for (iovec, mem) in chunks {
-            for offset in memmem::find_iter(&mem, &needle) {
-                let absolute = iovec.base + offset as u64;
-                matches.push(absolute);
-            }
+/// Encodes `string` as ASCII, UTF-8, and UTF-16LE candidate byte patterns
+///
+/// For ASCII-only input, the ASCII and UTF-8 encodings are byte-identical; the
+/// duplicate is dropped so the same match isn't reported twice.
+fn encode_candidates(string: &str) -> Vec<Vec<u8>> {
+    let utf8 = string.as_bytes().to_vec();
+    let ascii = string.is_ascii().then(|| utf8.clone());
+    let utf16le: Vec<u8> = string.encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect();
+
+    let mut candidates = Vec::new();
+    for needle in [ascii, Some(utf8), Some(utf16le)].into_iter().flatten() {
+        if !candidates.contains(&needle) {
+            candidates.push(needle);
         }
     }
 
-    crate::commands::print_and_save_results(s, matches);
-
-    Ok(())
+    candidates
 }
@@ -1,10 +1,7 @@
 //! Utilities for handlers
 
-use core::cmp::Ordering;
-use std::sync::{Arc, Mutex};
-use rayon::prelude::*;
-use crate::{Scanner, remote::IoVec, proc_maps::Region};
-use crate::num::{Constraint, Value};
+use crate::{Scanner, Pid, Refine, remote::IoVec, Modules};
+use crate::num::{Value, Constraint, RescanConstraint};
 
 /// Helper to extract a `T` from `arg` that generates nice error messages
 pub fn parse_arg<T: crate::num::ParseNumber>(arg: Option<&&str>, name: &str)
@@ -22,19 +19,8 @@ pub fn parse_value(arg: Option<&&str>) -> Result<crate::num::Value, String> {
         .ok_or("Missing or invalid type specifier".to_string())
 }
 
-/// Helper to extract constraints from `args` that generates nice error messages
-pub fn parse_constraints(args: &[&str], value: Value)
-        -> Result<Vec<Constraint>, String> {
-    if args.is_empty() { return Err("Constraints missing".to_string()); }
-
-    args.iter()
-        .map(|&c| Constraint::from_str_value(c, Some(value))
-            .map_err(|e| format!("Couldn't parse constraints: {:?}", e)))
-        .collect::<Result<Vec<Constraint>, String>>()
-}
-
 /// Print the results of a scan to the screen and save them in the scanner
-pub fn print_and_save_results(s: &mut Scanner, results: Vec<u64>) {
+pub fn print_and_save_results(s: &mut Scanner, results: Vec<(u64, u64)>) -> crate::commands::Result {
     // Print the results
     if results.is_empty() {
         println!("No results.");
@@ -43,98 +29,284 @@ pub fn print_and_save_results(s: &mut Scanner, results: Vec<u64>) {
             println!("Found {} results.", results.len());
         } else if results.len() == 1 {
             println!("Found 1 match at:");
-            print_results(s.pid(), &results, usize::MAX);
+            print_results(s.pid(), &results, usize::MAX)?;
         } else {
             println!("Found {:?} results at:", results.len());
-            print_results(s.pid(), &results, usize::MAX);
+            print_results(s.pid(), &results, usize::MAX)?;
         }
 
         // Save the results
-        s.results = results;
+        s.results.push(results);
     }
+
+    Ok(())
 }
 
 
-/// Print `num` `results` to the screen, showing possibly pointers mapped to a
-/// file (possibly static) in a different color
-pub fn print_results(pid: crate::Pid, results: &[u64], num: usize) {
-    if num == 0 { return; }
+/// Pairs each address in `addrs` with a freshly read raw `u64` at that
+/// address (`0` if it can't be read), for callers that only found addresses
+/// without a value of their own (pattern/string scans, `load`). Keeping a
+/// value alongside every address is what lets `refine`/`export`/etc. treat
+/// every history entry the same regardless of how it was produced.
+pub fn pair_with_fresh_values(pid: Pid, addrs: Vec<u64>) -> Vec<(u64, u64)> {
+    addrs.into_iter()
+        .map(|addr| {
+            let value = crate::remote::read(pid, addr, core::num::NonZero::new(8).unwrap())
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                .unwrap_or(0);
+            (addr, value)
+        })
+        .collect()
+}
+
+/// Print `num` `results` to the screen, resolving each address against the
+/// process's modules so pointers into a mapped file show up as
+/// `module+0xoffset` (or `module!symbol+0xoffset`) in a different color
+/// rather than as a raw address
+pub fn print_results(pid: crate::Pid, results: &[(u64, u64)], num: usize) -> crate::commands::Result {
+    if num == 0 { return Ok(()); }
 
-    // Get the regions that can contain mapped files pointers
-    let mut maps = crate::Maps::interesting_regions(pid).unwrap();
-    maps.0.retain(|reg| reg.is_likely_file_backed());
+    let modules = Modules::new(pid)
+        .map_err(|e| format!("Couldn't parse memory map: {:?}", e))?;
 
-    // Go through each address and print file pointers in a different color
-    for &addr in results.iter().take(num) {
-        if get_addr_region(&maps.0, addr).is_some() {
-            println!("\x1b[0;32m0x{addr:X}\x1b[0m");
+    for &(addr, _) in results.iter().take(num) {
+        if modules.find(addr).is_some() {
+            println!("\x1b[0;32m{}\x1b[0m", modules.format_addr(addr));
         } else {
             println!("0x{addr:X}");
         }
     }
+
+    Ok(())
 }
 
-/// Find out which region in `regions` an `addr` maps to
-pub fn get_addr_region(regions: &[Region], addr: u64) -> Option<&Region> {
-    // Binsearch for the matching region
-    regions.binary_search_by(|region| {
-        if region.addr.start > addr {
-            Ordering::Greater
-        } else if region.addr.start <= addr && region.addr.end > addr {
-            Ordering::Equal
-        } else {
-            Ordering::Less
+/// Parses a first-scan constraint out of `args`, templated off `value`'s type
+///
+/// Recognized forms: `== v`, `!= v`, `> v`, `< v`, a range `a..b`, or nothing
+/// at all / `unknown`, which keeps every slot regardless of its value.
+pub fn parse_constraints(args: &[&str], mut value: Value) -> Result<Vec<Constraint>, String> {
+    if args.first().is_none_or(|&op| op == "unknown") {
+        return Ok(vec![Constraint::Unknown]);
+    }
+
+    // A range is written as a single `a..b` token rather than an operator
+    if let Some((lo, hi)) = args[0].split_once("..") {
+        let mut lo_val = value;
+        lo_val.update_str(lo).map_err(|e| format!("Invalid range start: {:?}", e))?;
+
+        let mut hi_val = value;
+        hi_val.update_str(hi).map_err(|e| format!("Invalid range end: {:?}", e))?;
+
+        return Ok(vec![Constraint::Range(lo_val, hi_val)]);
+    }
+
+    let op = args[0];
+    let val_str = args.get(1).ok_or("Constraint value missing".to_string())?;
+    value.update_str(val_str)
+        .map_err(|e| format!("Invalid constraint value '{}': {:?}", val_str, e))?;
+
+    let constraint = match op {
+        "==" => Constraint::Eq(value),
+        "!=" => Constraint::Ne(value),
+        ">"  => Constraint::Gt(value),
+        "<"  => Constraint::Lt(value),
+        _    => return Err(format!("Unknown constraint operator '{}'", op)),
+    };
+
+    Ok(vec![constraint])
+}
+
+/// Parses a rescan constraint out of `args`, templated off `value`'s type
+///
+/// Recognized forms: `changed`, `unchanged`, `increased`, `decreased`,
+/// `increased-by N`, `decreased-by N`.
+pub fn parse_rescan_constraint(args: &[&str], mut value: Value)
+        -> Result<RescanConstraint, String> {
+    let kw = *args.first().ok_or("Rescan constraint missing".to_string())?;
+
+    match kw {
+        "changed"   => Ok(RescanConstraint::Changed),
+        "unchanged" => Ok(RescanConstraint::Unchanged),
+        "increased" => Ok(RescanConstraint::Increased),
+        "decreased" => Ok(RescanConstraint::Decreased),
+        "increased-by" | "decreased-by" => {
+            let val_str = args.get(1).ok_or("Delta missing".to_string())?;
+            value.update_str(val_str)
+                .map_err(|e| format!("Invalid delta '{}': {:?}", val_str, e))?;
+
+            Ok(if kw == "increased-by" {
+                RescanConstraint::IncreasedBy(value)
+            } else {
+                RescanConstraint::DecreasedBy(value)
+            })
         }
-    })
-    .ok()
-    .map(|idx| &regions[idx])
+        _ => Err(format!("Unknown rescan constraint '{}'", kw)),
+    }
 }
 
-/// Common utility function for scanning memory based on constraints
+/// Parses an untyped refine predicate out of `args`
+///
+/// Recognized forms: `changed`, `unchanged`, `increased`, `decreased`,
+/// `increased-by N`, `decreased-by N`. Unlike [`parse_rescan_constraint`],
+/// the delta is a bare `u64` rather than a typed [`Value`], since
+/// [`Refine`] compares raw re-reads recorded in [`Scanner::results`].
+pub fn parse_refine(args: &[&str]) -> Result<Refine, String> {
+    let kw = *args.first().ok_or("Refine predicate missing".to_string())?;
+
+    match kw {
+        "changed"   => Ok(Refine::Changed),
+        "unchanged" => Ok(Refine::Unchanged),
+        "increased" => Ok(Refine::Increased),
+        "decreased" => Ok(Refine::Decreased),
+        "increased-by" | "decreased-by" => {
+            let val_str = args.get(1).ok_or("Delta missing".to_string())?;
+            let delta = crate::num::parse::<u64>(val_str)
+                .map_err(|e| format!("Invalid delta '{}': {:?}", val_str, e))?;
+
+            Ok(if kw == "increased-by" {
+                Refine::IncreasedBy(delta)
+            } else {
+                Refine::DecreasedBy(delta)
+            })
+        }
+        _ => Err(format!("Unknown refine predicate '{}'", kw)),
+    }
+}
+
+/// Bytes carried between successive [`scan_batch`] calls over the same
+/// contiguous stream of `read_budget`-sized windows, so a value straddling a
+/// window boundary isn't dropped. Mirrors the carry-over technique in
+/// [`scan_contiguous`], but threaded explicitly since a numeric scan calls
+/// `scan_batch` once per window rather than handing it the whole iterator.
+///
+/// Start a fresh `ScanCarry` per contiguous stream (e.g. per region, if
+/// regions are scanned independently); reusing one across unrelated streams
+/// would wrongly stitch their boundaries together.
+#[derive(Default)]
+pub struct ScanCarry {
+    bytes: Vec<u8>,
+    prev_end: Option<u64>,
+}
+
+/// Reads `batch` and decodes each `value.bytes()`-aligned slot, recording
+/// `(address, value)` into `matches` for every slot satisfying `constraints`.
+///
+/// `carry` stitches a value that straddles the boundary between `batch` and
+/// the batch scanned in the previous call: at most `value.bytes() - 1`
+/// trailing bytes are kept and prepended to the next read of the same
+/// contiguous region, exactly as [`scan_contiguous`] does for pattern/string
+/// matches. Since the carried prefix is always shorter than one full value,
+/// no slot is ever double-counted.
 pub fn scan_batch(
-    pid: crate::Pid,
-    matches: &mut Vec<u64>,
+    pid: Pid,
+    matches: &mut Vec<(u64, Value)>,
     batch: &[IoVec],
-    value: Value,
+    mut value: Value,
     constraints: &[Constraint],
+    carry: &mut ScanCarry,
 ) {
-    // Read the memory
-    let memory = crate::remote::read_vecs(pid, &batch);
+    let memory = crate::remote::read_vecs(pid, batch);
 
-    // Retain only successfully read chunks
-    let chunks: Vec<_> = batch.iter()
-        .zip(memory.into_iter())
-        .filter_map(|(iovec, mem)| mem.map(|m| (iovec, m)))
-        .collect();
+    for (iovec, mem) in batch.iter().zip(memory.into_iter()) {
+        if mem.is_empty() {
+            // Unreadable region: can't carry bytes across a hole
+            carry.bytes.clear();
+            carry.prev_end = None;
+            continue;
+        }
 
-    // Shared matches vector with interior mutability
-    let results = Arc::new(Mutex::new(Vec::new()));
+        // Only carry bytes over if this read directly continues the region
+        // we carried bytes from
+        let contiguous = carry.prev_end == Some(iovec.base);
+        if !contiguous { carry.bytes.clear(); }
 
-    // Parallel iteration over chunks
-    chunks.par_iter().for_each(|(iovec, mem)| {
-        let mut local_results = Vec::new();
+        // The absolute address of the first byte of `combined`
+        let combined_base = iovec.base - carry.bytes.len() as u64;
 
-        // Local copy of the value
-        let mut v = value;
+        let mut combined = std::mem::take(&mut carry.bytes);
+        combined.extend_from_slice(&mem);
 
-        for (offset, chunk) in mem.chunks_exact(v.bytes()).enumerate() {
-            v.from_le_bytes(chunk);
+        for (idx, chunk) in combined.chunks_exact(value.bytes()).enumerate() {
+            value.from_le_bytes(chunk);
 
-            // Check constraints
-            if constraints.iter().all(|x| x.check(v)) {
-                let abs = iovec.base + offset as u64 * v.bytes() as u64;
-                local_results.push(abs);
+            if constraints.iter().all(|c| c.check(value)) {
+                let addr = combined_base + (idx * value.bytes()) as u64;
+                matches.push((addr, value));
             }
         }
 
-        // Append to global results
-        if !local_results.is_empty() {
-            let mut guard = results.lock().unwrap();
-            guard.extend(local_results);
+        // Carry the trailing bytes that didn't fill a whole slot into the next
+        // (possibly contiguous) read, so slot alignment is preserved.
+        // `mem` may be a partial prefix of `iovec` (see
+        // [`crate::remote::read_vecs`]), so advance `prev_end` by only
+        // however much was actually read
+        let keep = combined.len() % value.bytes();
+        carry.bytes = combined[combined.len() - keep..].to_vec();
+        carry.prev_end = Some(iovec.base + mem.len() as u64);
+    }
+}
+
+/// Scans a stream of [`IoVec`] batches (as produced by [`crate::Maps::chunks`])
+/// for occurrences reported by `find`, properly handling matches that
+/// straddle two separate reads of the same contiguous region.
+///
+/// `find` is run against each logical buffer and must return `(offset, len)`
+/// pairs for every occurrence found. Since a contiguous region can be split
+/// across multiple reads (both within a batch and across batches), the last
+/// `needle_len - 1` bytes of a read are carried over and prepended to the
+/// next read of the same contiguous region, so a needle straddling the split
+/// is not missed. Matches fully contained within the carried-over prefix are
+/// skipped, since they were already reported against the previous read.
+pub fn scan_contiguous(
+    pid: Pid,
+    chunks: impl Iterator<Item = Vec<IoVec>>,
+    needle_len: usize,
+    mut find: impl FnMut(&[u8]) -> Vec<(usize, usize)>,
+) -> Vec<u64> {
+    let mut matches = Vec::new();
+    let mut carry: Vec<u8> = Vec::new();
+    let mut prev_end: Option<u64> = None;
+
+    for batch in chunks {
+        // Read the memory for this batch
+        let memory = crate::remote::read_vecs(pid, &batch);
+
+        for (iovec, mem) in batch.iter().zip(memory.into_iter()) {
+            if mem.is_empty() {
+                // Unreadable region: can't carry bytes across a hole
+                carry.clear();
+                prev_end = None;
+                continue;
+            }
+
+            // Only carry bytes over if this read directly continues the
+            // region we carried bytes from
+            let contiguous = prev_end == Some(iovec.base);
+            let carry_len = if contiguous { carry.len() } else { 0 };
+            if !contiguous { carry.clear(); }
+
+            // The absolute address of the first byte of `combined`
+            let combined_base = iovec.base - carry_len as u64;
+
+            let mut combined = std::mem::take(&mut carry);
+            combined.extend_from_slice(&mem);
+
+            for (offset, len) in find(&combined) {
+                // Skip matches fully contained in the carried-over prefix:
+                // they were already reported against the previous read
+                if offset + len <= carry_len { continue; }
+                matches.push(combined_base + offset as u64);
+            }
+
+            // Carry the trailing bytes into the next (possibly contiguous)
+            // read. `mem` may be a partial prefix of `iovec` (see
+            // [`crate::remote::read_vecs`]), so advance `prev_end` by only
+            // however much was actually read
+            let keep = needle_len.saturating_sub(1).min(combined.len());
+            carry = combined[combined.len() - keep..].to_vec();
+            prev_end = Some(iovec.base + mem.len() as u64);
         }
-    });
+    }
 
-    // Move collected results back into matches
-    let mut guard = results.lock().unwrap();
-    matches.extend(guard.drain(..));
+    matches
 }
@@ -0,0 +1,48 @@
+use crate::remote;
+
+crate::register_command_handler!(
+    handler, ["set", "write"],
+    "Write a value to every address in the current scan results.",
+r#"`<type> <value>`
+* `type` - Value type letter (see `db`/`dw`/... for the list)
+* `value` - The value to write, parsed according to `type`
+"#
+);
+
+fn handler(s: &mut crate::Scanner, args: &[&str]) -> crate::commands::Result {
+    // Parse the value type and the value itself
+    let mut value = parse_write_value(args)?;
+
+    // Grab the current scan results
+    let results = s.results.last()
+        .filter(|r| !r.is_empty())
+        .ok_or("No scan results to write to".to_string())?;
+
+    // Write the value to every address in a single batch of vectored
+    // syscalls, counting how many succeeded
+    let bytes = value.to_le_bytes();
+    let writes: Vec<(u64, &[u8])> = results.iter()
+        .map(|&(addr, _)| (addr, bytes.as_slice()))
+        .collect();
+    let written = remote::write_vecs(s.pid(), &writes).iter().filter(|&&ok| ok).count();
+
+    println!("Wrote to {written}/{} addresses", results.len());
+
+    Ok(())
+}
+
+/// Parses `<type> <value>` out of `args[1..]`, mirroring the type-letter
+/// convention used by the `d*` display commands
+pub(crate) fn parse_write_value(args: &[&str]) -> Result<crate::num::Value, String> {
+    let letter = args.get(1)
+        .and_then(|s| s.chars().next())
+        .ok_or("Missing type specifier".to_string())?;
+
+    let mut value = crate::num::Value::default_from_letter(letter);
+
+    let val_str = args.get(2).ok_or("Value missing".to_string())?;
+    value.update_str(val_str)
+        .map_err(|e| format!("Invalid value: {:?}", e))?;
+
+    Ok(value)
+}
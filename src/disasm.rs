@@ -0,0 +1,79 @@
+//! x86-64 disassembly of remote memory
+//!
+//! Gated behind the `disasm` feature so the core scanner stays lean when a
+//! decoder isn't needed.
+
+use iced_x86::{Decoder, DecoderOptions, Formatter, NasmFormatter};
+use crate::Pid;
+
+/// A single decoded instruction
+#[derive(Debug)]
+pub struct Instruction {
+    /// The address this instruction was decoded from
+    pub addr: u64,
+
+    /// The raw bytes making up this instruction
+    pub bytes: Vec<u8>,
+
+    /// The instruction mnemonic, e.g. `mov`
+    pub mnemonic: String,
+
+    /// The formatted operands, e.g. `rax, rbx`
+    pub operands: String,
+}
+
+/// Errors that can occur while decoding remote memory
+#[derive(Debug)]
+pub enum DisasmError {
+    /// Couldn't read the target memory at all
+    ReadFailed,
+
+    /// A byte sequence couldn't be decoded into a valid instruction
+    InvalidInstruction(u8),
+}
+
+/// Decodes `count` x86-64 instructions starting at `addr` in `pid`
+///
+/// Reads enough memory up front to cover `count` instructions at their
+/// maximum possible length (15 bytes each), then decodes one instruction at
+/// a time, stopping early if fewer than `count` valid instructions are found
+/// before the read runs out.
+pub fn decode(pid: Pid, addr: u64, count: usize) -> Result<Vec<Instruction>, DisasmError> {
+    const MAX_INSTRUCTION_LEN: usize = 15;
+
+    let len = core::num::NonZero::new(count * MAX_INSTRUCTION_LEN)
+        .ok_or(DisasmError::ReadFailed)?;
+    let memory = crate::remote::read(pid, addr, len).ok_or(DisasmError::ReadFailed)?;
+
+    let mut decoder = Decoder::with_ip(64, &memory, addr, DecoderOptions::NONE);
+    let mut formatter = NasmFormatter::new();
+
+    let mut instructions = Vec::with_capacity(count);
+    let mut insn = iced_x86::Instruction::default();
+
+    while instructions.len() < count && decoder.can_decode() {
+        let start = decoder.position();
+        decoder.decode_out(&mut insn);
+
+        if insn.is_invalid() {
+            return Err(DisasmError::InvalidInstruction(memory[start]));
+        }
+
+        let end = decoder.position();
+
+        let mut formatted = String::new();
+        formatter.format(&insn, &mut formatted);
+        let (mnemonic, operands) = formatted.split_once(' ')
+            .map(|(m, o)| (m.to_string(), o.trim_start().to_string()))
+            .unwrap_or((formatted, String::new()));
+
+        instructions.push(Instruction {
+            addr: insn.ip(),
+            bytes: memory[start..end].to_vec(),
+            mnemonic,
+            operands,
+        });
+    }
+
+    Ok(instructions)
+}
@@ -0,0 +1,61 @@
+//! Serializable export of the scan history
+//!
+//! Hand-rolled JSON/CSV writers, in keeping with the rest of the crate not
+//! pulling in a serialization framework for simple, flat output formats.
+
+use std::io::{self, Write};
+use crate::Scanner;
+
+/// Export formats supported by [`Scanner::export`]
+#[derive(Debug, Clone, Copy)]
+pub enum ExportFormat {
+    /// A JSON array with one object per scan pass
+    Json,
+
+    /// One CSV row per match: `pass,address,value`
+    Csv,
+}
+
+impl Scanner {
+    /// Serializes the full scan history to `writer` in the given `format`.
+    pub fn export(&self, format: ExportFormat, mut writer: impl Write) -> io::Result<()> {
+        match format {
+            ExportFormat::Json => self.export_json(&mut writer),
+            ExportFormat::Csv  => self.export_csv(&mut writer),
+        }
+    }
+
+    /// Writes the history as a JSON array: `[{pid, pass, count, matches}]`
+    fn export_json(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "[")?;
+
+        let last = self.results.len().saturating_sub(1);
+        for (pass, matches) in self.results.iter().enumerate() {
+            let entries = matches.iter()
+                .map(|(a, v)| format!("{{\"address\":\"0x{a:x}\",\"value\":\"0x{v:x}\"}}"))
+                .collect::<Vec<_>>()
+                .join(",");
+
+            write!(writer,
+                "  {{\"pid\":{},\"pass\":{},\"count\":{},\"matches\":[{}]}}",
+                self.pid().0.get(), pass, matches.len(), entries)?;
+
+            writeln!(writer, "{}", if pass == last { "" } else { "," })?;
+        }
+
+        writeln!(writer, "]")
+    }
+
+    /// Streams the history as CSV rows, one per match
+    fn export_csv(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "pass,address,value")?;
+
+        for (pass, matches) in self.results.iter().enumerate() {
+            for &(addr, value) in matches {
+                writeln!(writer, "{pass},0x{addr:x},0x{value:x}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
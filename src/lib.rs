@@ -1,20 +1,57 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![feature(iter_intersperse)]
 
+extern crate alloc;
+
+#[macro_use] pub mod num;
+pub mod pattern;
+
+#[cfg(feature = "std")]
 use core::num::NonZero;
 
+#[cfg(feature = "std")]
 #[macro_use] pub mod commands;
-#[macro_use] pub mod num;
+#[cfg(feature = "std")]
 pub mod cli;
+#[cfg(feature = "std")]
 pub mod remote;
+#[cfg(feature = "std")]
 pub mod proc_maps;
+#[cfg(feature = "std")]
 pub use proc_maps::Maps;
+#[cfg(feature = "std")]
 mod scanner;
+#[cfg(feature = "std")]
 pub use scanner::*;
+#[cfg(feature = "std")]
+mod snapshot;
+#[cfg(feature = "std")]
+pub use snapshot::Snapshot;
+#[cfg(feature = "std")]
+mod export;
+#[cfg(feature = "std")]
+pub use export::ExportFormat;
+#[cfg(feature = "std")]
+pub mod worker;
+#[cfg(feature = "std")]
+mod refine;
+#[cfg(feature = "std")]
+pub use refine::Refine;
+#[cfg(feature = "std")]
+mod module;
+#[cfg(feature = "std")]
+pub use module::{Modules, Module};
+#[cfg(all(feature = "std", feature = "disasm"))]
+pub mod disasm;
+#[cfg(all(feature = "std", feature = "rayon"))]
+pub mod parallel;
 
 /// Wrapper around [`std::result::Result`] for this application
+#[cfg(feature = "std")]
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Generic error type for this application
+#[cfg(feature = "std")]
 #[derive(Debug)]
 pub enum Error {
     /// The specified PID is not a number
@@ -33,6 +70,7 @@ pub enum Error {
     Num(crate::num::Error),
 }
 
+#[cfg(feature = "std")]
 impl From<num::Error> for Error {
     fn from(val: num::Error) -> Self {
         Self::Num(val)
@@ -40,10 +78,12 @@ impl From<num::Error> for Error {
 }
 
 /// System process ID
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Copy)]
 #[repr(transparent)]
 pub struct Pid(pub NonZero<usize>);
 
+#[cfg(feature = "std")]
 impl TryFrom<&str> for Pid {
     type Error = Error;
 
@@ -3,21 +3,32 @@ use smug::{Pid, cli::Cli};
 fn main() -> smug::Result<()> {
     // Get the arguments
     let args = std::env::args().collect::<Vec<_>>();
-    if args.len() != 2 {
-        println!("Usage: {} <pid>", args.get(0).unwrap());
+
+    // Hidden worker mode: re-exec'd by a scan command to do the actual
+    // scanning in a crash-isolated child process (see `smug::worker`)
+    if args.get(1).map(String::as_str) == Some(smug::worker::WORKER_FLAG) {
+        if let Err(e) = smug::worker::run_worker() {
+            eprintln!("!!! {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if args.len() < 2 || args.len() > 3 {
+        println!("Usage: {} <pid> [read_budget_bytes]", args.get(0).unwrap());
         return Ok(());
     }
 
     // Get the requested pid
     let pid = Pid::try_from(args[1].as_str())?;
 
-    // // Create the CLI and run the application! yay
-    // let mut cli = Cli::new(pid, ">> ".to_string())?;
-    let mut regions = smug::proc_maps::Maps::rw_regions(pid).unwrap();
-    smug::read_remote::populate_regions(pid, regions.0.as_mut_slice());
-    for region in &regions.0 {
-        println!("{:X?} {:x?} {:?}", region.memory.is_some(), region.addr().start, region.path());
-    }
+    // An optional read budget override, in bytes (see `Scanner::with_read_budget`)
+    let read_budget = args.get(2)
+        .map(|s| smug::num::parse::<usize>(s))
+        .transpose()
+        .map_err(smug::Error::from)?;
 
-    Ok(())
+    // Create the CLI and run the application! yay
+    let mut cli = Cli::new(pid, ">> ".to_string(), read_budget)?;
+    cli.main_loop()
 }
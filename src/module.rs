@@ -0,0 +1,203 @@
+//! Module-relative addressing
+//!
+//! Groups consecutive file-backed [`crate::proc_maps::Region`]s into
+//! "modules" and resolves addresses to `module+0xoffset` (or
+//! `module!symbol+0xoffset` when an ELF symbol table is available), so that
+//! results survive ASLR and re-attaching to a restarted process.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use crate::{Pid, Maps};
+
+/// A group of consecutive memory regions backed by the same file
+#[derive(Debug, Clone)]
+pub struct Module {
+    /// The module's name, i.e. the last path component of its backing file
+    pub name: String,
+
+    /// The full path backing this module
+    pub path: String,
+
+    /// The lowest mapped address of this module
+    pub base: u64,
+
+    /// The highest mapped address (exclusive) of this module
+    pub end: u64,
+}
+
+impl Module {
+    /// Whether `addr` falls within this module's mapped range
+    fn contains(&self, addr: u64) -> bool {
+        (self.base..self.end).contains(&addr)
+    }
+}
+
+/// All modules currently mapped into a process
+#[derive(Debug, Clone)]
+pub struct Modules(pub Vec<Module>);
+
+impl Modules {
+    /// Groups `pid`'s file-backed regions into modules
+    pub fn new(pid: Pid) -> crate::Result<Self> {
+        let maps = Maps::all_regions(pid)?;
+
+        let mut modules: Vec<Module> = Vec::new();
+        for region in maps.0 {
+            // Only regions backed by a stable file are worth grouping
+            if !region.is_likely_file_backed() { continue; }
+            let path = region.path.clone().unwrap();
+
+            match modules.last_mut() {
+                // Extend the current module if this region directly
+                // continues it
+                Some(m) if m.path == path && m.end == region.addr.start => {
+                    m.end = region.addr.end;
+                }
+                // Otherwise this is the start of a new module
+                _ => {
+                    let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+                    modules.push(Module {
+                        name,
+                        path,
+                        base: region.addr.start,
+                        end: region.addr.end,
+                    });
+                }
+            }
+        }
+
+        Ok(Self(modules))
+    }
+
+    /// Finds the module containing `addr`, if any
+    pub fn find(&self, addr: u64) -> Option<&Module> {
+        self.0.iter().find(|m| m.contains(addr))
+    }
+
+    /// Finds a module by name
+    pub fn by_name(&self, name: &str) -> Option<&Module> {
+        self.0.iter().find(|m| m.name == name)
+    }
+
+    /// Formats `addr` as `module+0xoffset`, resolved to the nearest
+    /// preceding ELF symbol when one is available, or as a raw address when
+    /// it doesn't fall within any known module
+    pub fn format_addr(&self, addr: u64) -> String {
+        let Some(module) = self.find(addr) else {
+            return format!("0x{addr:x}");
+        };
+
+        let offset = addr - module.base;
+
+        match symbol_for(&module.path, offset) {
+            Some((name, 0))     => format!("{}!{name}", module.name),
+            Some((name, delta)) => format!("{}!{name}+0x{delta:x}", module.name),
+            None                => format!("{}+0x{offset:x}", module.name),
+        }
+    }
+}
+
+/// Cache of parsed ELF symbols, keyed by the file path they came from
+static SYMBOL_CACHE: OnceLock<Mutex<HashMap<String, Option<Vec<(u64, String)>>>>> =
+    OnceLock::new();
+
+/// Resolves `offset` into the nearest preceding symbol in the ELF at `path`,
+/// returning `(symbol_name, delta)`
+fn symbol_for(path: &str, offset: u64) -> Option<(String, u64)> {
+    let cache = SYMBOL_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let symbols = cache.lock().unwrap()
+        .entry(path.to_string())
+        .or_insert_with(|| parse_elf_symbols(path))
+        .clone()?;
+
+    // Find the last symbol whose address doesn't exceed `offset`
+    let idx = symbols.partition_point(|&(addr, _)| addr <= offset);
+    if idx == 0 { return None; }
+
+    let (addr, name) = &symbols[idx - 1];
+    Some((name.clone(), offset - addr))
+}
+
+fn read_u16(data: &[u8], off: usize) -> Option<u16> {
+    data.get(off..off + 2).map(|b| u16::from_le_bytes(b.try_into().unwrap()))
+}
+
+fn read_u32(data: &[u8], off: usize) -> Option<u32> {
+    data.get(off..off + 4).map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+}
+
+fn read_u64(data: &[u8], off: usize) -> Option<u64> {
+    data.get(off..off + 8).map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+}
+
+/// Minimal hand-rolled ELF64 `.symtab`/`.dynsym` reader
+///
+/// Returns symbols sorted by address, preferring `.symtab` over `.dynsym`
+/// when both are present. Returns `None` if the file isn't a 64-bit ELF or
+/// carries no symbol table at all (e.g. it was stripped).
+fn parse_elf_symbols(path: &str) -> Option<Vec<(u64, String)>> {
+    const SHT_SYMTAB: u32 = 2;
+    const SHT_DYNSYM: u32 = 11;
+
+    let data = std::fs::read(path).ok()?;
+
+    // Only 64-bit, little-endian ELFs are supported
+    if data.get(0..4) != Some(b"\x7fELF") || data.get(4) != Some(&2)
+            || data.get(5) != Some(&1) { return None; }
+
+    let e_shoff     = read_u64(&data, 40)? as usize;
+    let e_shentsize = read_u16(&data, 58)? as usize;
+    let e_shnum     = read_u16(&data, 60)? as usize;
+
+    if e_shoff == 0 || e_shnum == 0 || e_shentsize == 0 { return None; }
+
+    let section = |idx: usize| data.get(e_shoff + idx * e_shentsize
+        ..e_shoff + (idx + 1) * e_shentsize);
+
+    // Prefer `.symtab`, fall back to `.dynsym`
+    let mut sym_idx = None;
+    for idx in 0..e_shnum {
+        let sh = section(idx)?;
+        match read_u32(sh, 4)? {
+            SHT_SYMTAB => { sym_idx = Some(idx); break; }
+            SHT_DYNSYM if sym_idx.is_none() => sym_idx = Some(idx),
+            _ => {}
+        }
+    }
+    let sym_sh = section(sym_idx?)?;
+
+    let sh_offset  = read_u64(sym_sh, 24)? as usize;
+    let sh_size    = read_u64(sym_sh, 32)? as usize;
+    let sh_link    = read_u32(sym_sh, 40)? as usize;
+    let sh_entsize = read_u64(sym_sh, 56)? as usize;
+
+    if sh_entsize == 0 { return None; }
+
+    let str_sh = section(sh_link)?;
+    let str_off  = read_u64(str_sh, 24)? as usize;
+    let str_size = read_u64(str_sh, 32)? as usize;
+    let strtab = data.get(str_off..str_off + str_size)?;
+
+    // Elf64_Sym: st_name(4) st_info(1) st_other(1) st_shndx(2) st_value(8)
+    // st_size(8) -- 24 bytes total
+    let mut symbols = Vec::new();
+    for i in 0..(sh_size / sh_entsize) {
+        let sym = data.get(sh_offset + i * sh_entsize..sh_offset + i * sh_entsize + 24)?;
+
+        let st_name  = read_u32(sym, 0)? as usize;
+        let st_value = read_u64(sym, 8)?;
+
+        // Skip undefined symbols (no address to resolve against)
+        if st_value == 0 { continue; }
+
+        let name_bytes = strtab.get(st_name..)?;
+        let end = name_bytes.iter().position(|&b| b == 0)?;
+        if end == 0 { continue; }
+
+        let name = String::from_utf8_lossy(&name_bytes[..end]).into_owned();
+        symbols.push((st_value, name));
+    }
+
+    symbols.sort_unstable_by_key(|&(addr, _)| addr);
+    Some(symbols)
+}
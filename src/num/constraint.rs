@@ -0,0 +1,78 @@
+//! Constraints for filtering scanned values
+
+use crate::num::Value;
+
+/// An absolute constraint applied to each slot during a first scan
+#[derive(Debug, Clone, Copy)]
+pub enum Constraint {
+    /// The initial value is unknown; keep every aligned slot
+    Unknown,
+
+    /// The value must equal the given one
+    Eq(Value),
+
+    /// The value must not equal the given one
+    Ne(Value),
+
+    /// The value must be greater than the given one
+    Gt(Value),
+
+    /// The value must be less than the given one
+    Lt(Value),
+
+    /// The value must fall within the inclusive range `a..=b`
+    Range(Value, Value),
+}
+
+impl Constraint {
+    /// Checks whether `value` satisfies this constraint
+    pub fn check(&self, value: Value) -> bool {
+        match *self {
+            Self::Unknown       => true,
+            Self::Eq(v)         => value == v,
+            Self::Ne(v)         => value != v,
+            Self::Gt(v)         => value.cmp_exact(&v).is_gt(),
+            Self::Lt(v)         => value.cmp_exact(&v).is_lt(),
+            Self::Range(lo, hi) =>
+                value.cmp_exact(&lo).is_ge() && value.cmp_exact(&hi).is_le(),
+        }
+    }
+}
+
+/// A relative constraint applied to a previously-recorded value during a
+/// rescan
+#[derive(Debug, Clone, Copy)]
+pub enum RescanConstraint {
+    /// The value changed since the last scan
+    Changed,
+
+    /// The value stayed the same since the last scan
+    Unchanged,
+
+    /// The value increased since the last scan
+    Increased,
+
+    /// The value decreased since the last scan
+    Decreased,
+
+    /// The value increased by exactly this amount since the last scan
+    IncreasedBy(Value),
+
+    /// The value decreased by exactly this amount since the last scan
+    DecreasedBy(Value),
+}
+
+impl RescanConstraint {
+    /// Checks whether `current` satisfies this constraint relative to
+    /// `previous`
+    pub fn check(&self, previous: Value, current: Value) -> bool {
+        match *self {
+            Self::Changed     => current != previous,
+            Self::Unchanged   => current == previous,
+            Self::Increased   => current.cmp_exact(&previous).is_gt(),
+            Self::Decreased   => current.cmp_exact(&previous).is_lt(),
+            Self::IncreasedBy(delta) => current.wrapping_sub(&previous) == delta,
+            Self::DecreasedBy(delta) => previous.wrapping_sub(&current) == delta,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Parsing of plain integers from command arguments
+
+/// Types [`parse`] knows how to parse from a string
+///
+/// Implemented for every primitive integer type so `parse` can produce the
+/// right [`crate::num::Error`] variant (signed vs. unsigned) on failure.
+pub trait ParseNumber: Sized {
+    /// Parses `s` in the given `radix`
+    fn parse_radix(s: &str, radix: u32) -> Result<Self, core::num::ParseIntError>;
+
+    /// Wraps a parse failure in the appropriate [`crate::num::Error`] variant
+    fn parse_error(e: core::num::ParseIntError) -> crate::num::Error;
+}
+
+macro_rules! impl_parse_number {
+    (unsigned: $($ty:ty),+) => {
+        $(impl ParseNumber for $ty {
+            fn parse_radix(s: &str, radix: u32) -> Result<Self, core::num::ParseIntError> {
+                <$ty>::from_str_radix(s, radix)
+            }
+
+            fn parse_error(e: core::num::ParseIntError) -> crate::num::Error {
+                crate::num::Error::ParseUnsigned(e)
+            }
+        })+
+    };
+    (signed: $($ty:ty),+) => {
+        $(impl ParseNumber for $ty {
+            fn parse_radix(s: &str, radix: u32) -> Result<Self, core::num::ParseIntError> {
+                <$ty>::from_str_radix(s, radix)
+            }
+
+            fn parse_error(e: core::num::ParseIntError) -> crate::num::Error {
+                crate::num::Error::ParseSigned(e)
+            }
+        })+
+    };
+}
+
+impl_parse_number!(unsigned: u8, u16, u32, u64, usize);
+impl_parse_number!(signed: i8, i16, i32, i64, isize);
+
+/// Parses `s` as a `T`, supporting an optional `0x` hex prefix and otherwise
+/// treating `s` as decimal
+pub fn parse<T: ParseNumber>(s: &str) -> crate::num::Result<T> {
+    let (digits, radix) = match s.strip_prefix("0x") {
+        Some(rest) => (rest, 16),
+        None => (s, 10),
+    };
+
+    T::parse_radix(digits, radix).map_err(T::parse_error)
+}
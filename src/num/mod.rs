@@ -9,20 +9,23 @@ pub use int::*;
 mod constraint;
 pub use constraint::*;
 
+/// Wrapper around [`core::result::Result`] for this library
+pub type Result<T> = core::result::Result<T, Error>;
+
 /// Errors encountered in these libraries
 #[derive(Debug)]
 pub enum Error {
     /// Failed to parse a signed value
-    ParseSigned(std::num::ParseIntError),
+    ParseSigned(core::num::ParseIntError),
 
     /// Failed to parse an unsigned value
-    ParseUnsigned(std::num::ParseIntError),
+    ParseUnsigned(core::num::ParseIntError),
 
     /// Integer truncation happened when converting a `u64` to a `usize`
     TooBig,
 
     /// Failed to parse a floating point value
-    ParseFloat(std::num::ParseFloatError),
+    ParseFloat(core::num::ParseFloatError),
 
     /// Invalid constraint
     InvalidConstraint,
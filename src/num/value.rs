@@ -1,5 +1,5 @@
-use std::str::FromStr;
-use crate::{Result, Error};
+use core::str::FromStr;
+use alloc::vec::Vec;
 use crate::num::*;
 
 /// Different values
@@ -52,6 +52,52 @@ impl Value {
         }
     }
 
+    /// Compares `self` to `other` in their own width, rather than casting
+    /// both through `f64` first (which silently loses precision for
+    /// `U64`/`I64` magnitudes above 2^53). Used by [`Constraint`] so a
+    /// `>`/`<`/range check against a large 64-bit counter is exact.
+    ///
+    /// Panics if `self` and `other` are different variants; a `Constraint`'s
+    /// value is always parsed as the same type being scanned.
+    pub fn cmp_exact(&self, other: &Value) -> core::cmp::Ordering {
+        match (self, other) {
+            (Self::F32(a), Self::F32(b)) => a.partial_cmp(b).unwrap(),
+            (Self::F64(a), Self::F64(b)) => a.partial_cmp(b).unwrap(),
+            (Self::U8 (a), Self::U8 (b)) => a.cmp(b),
+            (Self::U16(a), Self::U16(b)) => a.cmp(b),
+            (Self::U32(a), Self::U32(b)) => a.cmp(b),
+            (Self::U64(a), Self::U64(b)) => a.cmp(b),
+            (Self::I8 (a), Self::I8 (b)) => a.cmp(b),
+            (Self::I16(a), Self::I16(b)) => a.cmp(b),
+            (Self::I32(a), Self::I32(b)) => a.cmp(b),
+            (Self::I64(a), Self::I64(b)) => a.cmp(b),
+            _ => unreachable!("compared values of different types"),
+        }
+    }
+
+    /// Computes `self - other` in the value's own width, wrapping for
+    /// integers, rather than subtracting in `f64` (which silently loses
+    /// precision for `U64`/`I64` magnitudes above 2^53). Used by
+    /// [`RescanConstraint::IncreasedBy`]/[`RescanConstraint::DecreasedBy`]
+    /// so a delta against a large 64-bit counter is exact.
+    ///
+    /// Panics if `self` and `other` are different variants.
+    pub fn wrapping_sub(&self, other: &Value) -> Value {
+        match (self, other) {
+            (Self::F32(a), Self::F32(b)) => Self::F32(a - b),
+            (Self::F64(a), Self::F64(b)) => Self::F64(a - b),
+            (Self::U8 (a), Self::U8 (b)) => Self::U8 (a.wrapping_sub(*b)),
+            (Self::U16(a), Self::U16(b)) => Self::U16(a.wrapping_sub(*b)),
+            (Self::U32(a), Self::U32(b)) => Self::U32(a.wrapping_sub(*b)),
+            (Self::U64(a), Self::U64(b)) => Self::U64(a.wrapping_sub(*b)),
+            (Self::I8 (a), Self::I8 (b)) => Self::I8 (a.wrapping_sub(*b)),
+            (Self::I16(a), Self::I16(b)) => Self::I16(a.wrapping_sub(*b)),
+            (Self::I32(a), Self::I32(b)) => Self::I32(a.wrapping_sub(*b)),
+            (Self::I64(a), Self::I64(b)) => Self::I64(a.wrapping_sub(*b)),
+            _ => unreachable!("subtracted values of different types"),
+        }
+    }
+
     /// Get number of bytes per `self`
     pub fn bytes(&self) -> usize {
         match self {
@@ -84,6 +130,22 @@ impl Value {
         }
     }
 
+    /// Encode the value as little-endian bytes
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::F32(val) => val.to_le_bytes().to_vec(),
+            Self::F64(val) => val.to_le_bytes().to_vec(),
+            Self::U8 (val) => val.to_le_bytes().to_vec(),
+            Self::U16(val) => val.to_le_bytes().to_vec(),
+            Self::U32(val) => val.to_le_bytes().to_vec(),
+            Self::U64(val) => val.to_le_bytes().to_vec(),
+            Self::I8 (val) => val.to_le_bytes().to_vec(),
+            Self::I16(val) => val.to_le_bytes().to_vec(),
+            Self::I32(val) => val.to_le_bytes().to_vec(),
+            Self::I64(val) => val.to_le_bytes().to_vec(),
+        }
+    }
+
     /// Update value from little-endian bytes
     pub fn from_le_bytes(&mut self, bytes: &[u8]) {
         match self {
@@ -111,17 +173,15 @@ impl Value {
     }
 
     /// Update `self` to a new value of the same type from `s`
-    pub fn update_str(&mut self, s: &str) -> Result<()> {
+    pub fn update_str(&mut self, s: &str) -> crate::num::Result<()> {
         match self {
             Self::F32(val) => {
                 *val = f32::from_str(s)
-                    .map_err(crate::num::Error::ParseFloat)
-                    .map_err(Error::Num)?;
+                    .map_err(crate::num::Error::ParseFloat)?;
             }
             Self::F64(val) => {
                 *val = f64::from_str(s)
-                    .map_err(crate::num::Error::ParseFloat)
-                    .map_err(Error::Num)?;
+                    .map_err(crate::num::Error::ParseFloat)?;
             }
             Self::U8 (val) => *val = parse::<u8>(s)?,
             Self::U16(val) => *val = parse::<u16>(s)?,
@@ -137,8 +197,8 @@ impl Value {
     }
 }
 
-impl std::fmt::Display for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::F32(val) =>
                 f.write_fmt(format_args!("{:25.6}", val)),
@@ -156,8 +216,8 @@ impl std::fmt::Display for Value {
     }
 }
 
-impl std::fmt::LowerHex for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::LowerHex for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::F32(val) =>
                 f.write_fmt(format_args!("{:08x}", val.to_bits())),
@@ -0,0 +1,87 @@
+//! Rayon-backed parallel region scanning
+//!
+//! Splits the target's readable regions across rayon's work-stealing pool,
+//! the way `bottom` and `ddh` spread their own per-region work with
+//! `par_iter`. Each worker reads and scans its own regions independently,
+//! so one slow or sparsely-mapped region never blocks the rest of the scan.
+
+use core::num::NonZero;
+use core::ops::Range;
+use rayon::prelude::*;
+use crate::{Pid, Maps, CHUNK_SIZE, proc_maps::Region, remote::IoVec};
+use crate::num::{Value, Constraint};
+use crate::commands::{scan_batch, ScanCarry};
+
+/// Splits the part of `region` that falls within `range` into iovecs of at
+/// most `budget` bytes (capped to [`CHUNK_SIZE`]). A single iovec never
+/// straddles a region boundary, since it's built from one region at a time.
+fn region_iovecs(region: &Region, range: &Range<u64>, budget: usize) -> Vec<IoVec> {
+    let budget = budget.min(CHUNK_SIZE) as u64;
+    let mut start = region.addr.start.max(range.start);
+    let end = region.addr.end.min(range.end);
+
+    let mut iovecs = Vec::new();
+    while start < end {
+        let len = (end - start).min(budget);
+        let Some(len_nz) = NonZero::new(len as usize) else { break; };
+        iovecs.push(IoVec::new(start, len_nz));
+        start += len;
+    }
+
+    iovecs
+}
+
+/// Scans one region for slots satisfying `constraints`, reading it in
+/// `budget`-sized windows and batching its iovecs at no more than
+/// [`crate::IOV_MAX`] per `process_vm_readv()` call. A `ScanCarry` is shared
+/// across every window of the region so a value straddling a window
+/// boundary is still found. A region that comes back short (partially
+/// unmapped, raced with `munmap`, ...) simply yields fewer matches rather
+/// than aborting the scan.
+fn scan_region(
+    pid: Pid,
+    region: &Region,
+    range: &Range<u64>,
+    value: Value,
+    constraints: &[Constraint],
+    budget: usize,
+) -> Vec<(u64, Value)> {
+    let mut matches = Vec::new();
+    let mut carry = ScanCarry::default();
+
+    for batch in region_iovecs(region, range, budget).chunks(*crate::IOV_MAX.get().unwrap()) {
+        scan_batch(pid, &mut matches, batch, value, constraints, &mut carry);
+    }
+
+    matches
+}
+
+/// Scans `maps` for slots within `range` satisfying `constraints`, reading
+/// at most `budget` bytes per `process_vm_readv()` call and splitting the
+/// regions across rayon's global pool. Falls back to scanning serially on
+/// the calling thread when the pool only has one thread, so there's no
+/// parallel overhead to pay on a single-core box.
+pub fn scan_parallel(
+    pid: Pid,
+    maps: &Maps,
+    range: Range<u64>,
+    value: Value,
+    constraints: &[Constraint],
+    budget: usize,
+) -> Vec<(u64, Value)> {
+    if rayon::current_num_threads() <= 1 {
+        return maps.0.iter()
+            .flat_map(|region| scan_region(pid, region, &range, value, constraints, budget))
+            .collect();
+    }
+
+    // Each worker scans its own region into a thread-local `Vec`; flatten
+    // and sort by address afterwards to restore the ascending order a
+    // serial scan would have produced
+    let mut matches: Vec<(u64, Value)> = maps.0.par_iter()
+        .flat_map(|region| scan_region(pid, region, &range, value, constraints, budget))
+        .collect();
+
+    matches.sort_unstable_by_key(|&(addr, _)| addr);
+    matches
+}
@@ -1,10 +1,56 @@
 //! `/proc/pid/maps` parser and stuff
-
+//!
+//! Reads the maps file with raw `open`/`read`/`close` syscalls rather than
+//! `rustix` or a procfs dependency. There's no `Cargo.toml` in this tree to
+//! even add `rustix` to, and every other kernel-facing module here
+//! (`remote`, `scanner`) already talks to the kernel through hand-rolled
+//! `extern "C"` declarations instead of a syscall wrapper crate, so this
+//! follows that precedent rather than the literal crate suggestion.
+
+use core::ffi::{c_char, c_int};
 use core::num::NonZero;
 use core::ops::Range;
+use std::ffi::CString;
 use std::fmt;
 use crate::{Error, Pid, remote::IoVec, CHUNK_SIZE};
 
+const O_RDONLY: c_int = 0;
+
+unsafe extern "C" {
+    fn open(path: *const c_char, flags: c_int) -> c_int;
+    fn read(fd: c_int, buf: *mut u8, count: usize) -> isize;
+    fn close(fd: c_int) -> c_int;
+}
+
+/// Reads `path` in full using raw syscalls
+fn read_file(path: &str) -> crate::Result<String> {
+    let cpath = CString::new(path)
+        .map_err(|_| Error::Io(std::io::Error::from(std::io::ErrorKind::InvalidInput)))?;
+
+    let fd = unsafe { open(cpath.as_ptr(), O_RDONLY) };
+    if fd < 0 {
+        return Err(Error::Io(std::io::Error::last_os_error()));
+    }
+
+    let mut contents = Vec::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = unsafe { read(fd, buf.as_mut_ptr(), buf.len()) };
+        if n < 0 {
+            let err = std::io::Error::last_os_error();
+            unsafe { close(fd) };
+            return Err(Error::Io(err));
+        } else if n == 0 {
+            break;
+        }
+        contents.extend_from_slice(&buf[..n as usize]);
+    }
+    unsafe { close(fd) };
+
+    String::from_utf8(contents)
+        .map_err(|_| Error::Io(std::io::Error::from(std::io::ErrorKind::InvalidData)))
+}
+
 /// Memory permissions
 #[derive(Debug, Clone)]
 pub struct Permissions {
@@ -44,6 +90,51 @@ impl fmt::Display for Permissions {
     }
 }
 
+/// What a region of memory is backed by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    /// The process heap (`[heap]`)
+    Heap,
+
+    /// A thread stack (`[stack]`, `[stack:<tid>]`)
+    Stack,
+
+    /// Backed by a regular file on disk
+    File,
+
+    /// Anonymous memory not covered by the other pseudo-path kinds
+    Anon,
+
+    /// Anything else (`[vdso]`, `[vsyscall]`, `[vvar]`, ...)
+    Other,
+}
+
+impl RegionKind {
+    /// Parses a `--<kind>` scan flag (e.g. `--heap`) into a [`RegionKind`]
+    pub fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "--heap"  => Some(Self::Heap),
+            "--stack" => Some(Self::Stack),
+            "--file"  => Some(Self::File),
+            "--anon"  => Some(Self::Anon),
+            _ => None,
+        }
+    }
+
+    /// The inverse of [`RegionKind::from_flag`], used to forward the filter
+    /// to the scan worker subprocess. `Other` has no flag, since it isn't a
+    /// selectable scan target.
+    pub fn as_flag(&self) -> Option<&'static str> {
+        match self {
+            Self::Heap  => Some("--heap"),
+            Self::Stack => Some("--stack"),
+            Self::File  => Some("--file"),
+            Self::Anon  => Some("--anon"),
+            Self::Other => None,
+        }
+    }
+}
+
 /// A region of memory in `/proc/pid/maps`
 #[derive(Debug, Clone)]
 pub struct Region {
@@ -164,6 +255,17 @@ impl Region {
         // Otherwise, looks like a real, stable file-backed mapping
         true
     }
+
+    /// Classifies what this region is backed by
+    pub fn kind(&self) -> RegionKind {
+        match self.path.as_deref() {
+            None => RegionKind::Anon,
+            Some("[heap]") => RegionKind::Heap,
+            Some(p) if p.starts_with("[stack") => RegionKind::Stack,
+            Some(p) if p.starts_with('[') => RegionKind::Other,
+            Some(_) => RegionKind::File,
+        }
+    }
 }
 
 impl fmt::Display for Region {
@@ -204,7 +306,7 @@ impl Maps {
     where
         F: FnMut(&Region) -> bool,
     {
-        let maps = std::fs::read_to_string(Self::path(pid)).map_err(Error::Io)?
+        let maps = read_file(&Self::path(pid))?
             .lines()
             .filter_map(Region::from_line)
             .filter(filter)
@@ -218,6 +320,14 @@ impl Maps {
         Self::regions(pid, |reg| reg.perms.read && reg.perms.write)
     }
 
+    /// Parse memory regions for `pid` and retain only the "interesting" ones
+    /// (see [`Maps::interesting_regions`]) backed by `kind`
+    pub fn regions_of_kind(pid: Pid, kind: RegionKind) -> crate::Result<Self> {
+        let mut maps = Self::interesting_regions(pid)?;
+        maps.0.retain(|reg| reg.kind() == kind);
+        Ok(maps)
+    }
+
     /// Parse memory regions for `pid` and retain only the readable ones
     pub fn r_regions(pid: Pid) -> crate::Result<Self> {
         Self::regions(pid, |reg| reg.perms.read)
@@ -238,9 +348,12 @@ impl Maps {
         Self::regions(pid, |_| true)
     }
 
-    /// Returns an iterator over groups of IoVecs where each group fits within
-    /// [`CHUNK_SIZE`] bytes and lies within `range`.
-    pub fn chunks(self, range: Range<u64>) -> impl Iterator<Item = Vec<IoVec>> {
+    /// Returns an iterator over groups of IoVecs where each group fits
+    /// within `budget` bytes (capped to [`CHUNK_SIZE`]) and lies within
+    /// `range`
+    pub fn chunks(self, range: Range<u64>, budget: usize) -> impl Iterator<Item = Vec<IoVec>> {
+        let budget = budget.min(CHUNK_SIZE) as u64;
+
         let mut regions: Vec<Range<u64>> = self.0.into_iter()
             .map(|r| {
                 let start = r.addr.start.max(range.start);
@@ -253,7 +366,7 @@ impl Maps {
         // The actual iterator
         std::iter::from_fn(move || {
             let mut batch = Vec::new();
-            let mut remaining = CHUNK_SIZE as u64;
+            let mut remaining = budget;
 
             while let Some(region) = regions.first_mut() {
                 let region_len = region.end - region.start;
@@ -0,0 +1,74 @@
+//! Comparative narrowing of the most recent scan results by value delta
+//!
+//! Complements the typed `u*` rescan commands (see
+//! [`crate::num::RescanConstraint`]) with a simpler, type-agnostic pass over
+//! [`Scanner::results`](crate::Scanner::results): re-read the addresses from
+//! the last history entry as raw `u64`s, compare each to the value recorded
+//! for it, and keep only the survivors as a new entry.
+
+use core::num::NonZero;
+use crate::{Scanner, remote::IoVec};
+
+/// A predicate comparing a re-read `u64` against its previously recorded
+/// value, used by [`Scanner::refine`]
+#[derive(Debug, Clone, Copy)]
+pub enum Refine {
+    /// The value is the same as before
+    Unchanged,
+    /// The value is different from before
+    Changed,
+    /// The value is greater than before
+    Increased,
+    /// The value is less than before
+    Decreased,
+    /// The value increased by exactly this amount
+    IncreasedBy(u64),
+    /// The value decreased by exactly this amount
+    DecreasedBy(u64),
+}
+
+impl Refine {
+    fn check(&self, previous: u64, current: u64) -> bool {
+        match *self {
+            Self::Unchanged => current == previous,
+            Self::Changed => current != previous,
+            Self::Increased => current > previous,
+            Self::Decreased => current < previous,
+            Self::IncreasedBy(delta) => current.wrapping_sub(previous) == delta,
+            Self::DecreasedBy(delta) => previous.wrapping_sub(current) == delta,
+        }
+    }
+}
+
+impl Scanner {
+    /// Re-reads the addresses from the most recent history entry as raw
+    /// `u64`s, keeps only those satisfying `predicate` against their
+    /// previously recorded value, and pushes the survivors as a new history
+    /// entry.
+    ///
+    /// Addresses whose region is no longer mapped are silently dropped
+    /// rather than erroring, the same way a `u*` rescan drops holes.
+    pub fn refine(&mut self, predicate: Refine) {
+        let previous = match self.results.last() {
+            Some(entry) if !entry.is_empty() => entry.clone(),
+            _ => return,
+        };
+
+        let len = NonZero::new(8).unwrap();
+        let iovecs: Vec<IoVec> = previous.iter()
+            .map(|&(addr, _)| IoVec::new(addr, len))
+            .collect();
+        let memory = crate::remote::read_vecs(self.pid(), &iovecs);
+
+        let mut survivors = Vec::new();
+        for (&(addr, prev_value), mem) in previous.iter().zip(memory.into_iter()) {
+            if mem.len() != 8 { continue; }
+            let current = u64::from_le_bytes(mem.try_into().unwrap());
+            if predicate.check(prev_value, current) {
+                survivors.push((addr, current));
+            }
+        }
+
+        self.results.push(survivors);
+    }
+}
@@ -32,6 +32,16 @@ unsafe extern "C" {
         remote_count: usize,
         flags:        usize,
     ) -> isize;
+
+    /// The raw `process_vm_writev()` syscall
+    pub fn process_vm_writev(
+        pid:          Pid,
+        local:        *const IoVec,
+        local_count:  usize,
+        remote:       *const IoVec,
+        remote_count: usize,
+        flags:        usize,
+    ) -> isize;
 }
 
 /// Attempts to read `len` of data at `addr` from a remote process
@@ -60,18 +70,49 @@ pub fn read(pid: Pid, addr: u64, len: NonZero<usize>) -> Option<Vec<u8>> {
     }
 }
 
+/// A page, the granularity [`recover_prefix`] bisects down to
+const PAGE_SIZE: u64 = 4096;
+
 /// Reads memory from the specified `remote` iovecs into local buffers.
 ///
-/// Each remote iovec maps 1:1 to a local buffer of the same size.
+/// Each remote iovec maps 1:1 to a returned buffer. Mirroring
+/// [`std::io::Read::read`]'s partial-read semantics, a buffer may be shorter
+/// than its iovec's length (down to empty) if only a prefix of that region
+/// could actually be read; it is never longer.
+///
+/// If a batched `process_vm_readv()` call comes up short on an iovec, the
+/// unread remainder of that iovec is bisected and retried down to page
+/// granularity, recovering as much of a sparsely-mapped or
+/// partially-unreadable region as possible before giving up on it.
 ///
-/// If a region is invalid, it's skipped, and the function retries with
-/// the remaining valid regions.
+/// `remote` is internally split into back-to-back batches of at most
+/// [`crate::IOV_MAX`] iovecs, so callers don't need to track the kernel
+/// limit themselves; the per-iovec results of each batch are concatenated in
+/// order.
 ///
-/// Will panic if no remote iovecs are provided or if more than
-/// [`crate::IOV_MAX`] are provided.
-pub fn read_vecs(pid: Pid, remote: &[IoVec]) -> Vec<Option<Vec<u8>>> {
+/// Will panic if no remote iovecs are provided.
+pub fn read_vecs(pid: Pid, remote: &[IoVec]) -> Vec<Vec<u8>> {
     assert!(remote.len() > 0);
-    assert!(*crate::IOV_MAX.get().unwrap() >= remote.len());
+
+    remote.chunks(*crate::IOV_MAX.get().unwrap())
+        .flat_map(|batch| read_vecs_capped(pid, batch))
+        .collect()
+}
+
+/// Reads memory from a single batch of at most [`crate::IOV_MAX`] `remote`
+/// iovecs, as a sequence of `process_vm_readv()` calls over shrinking index
+/// ranges. See [`read_vecs`].
+///
+/// Holes are recovered via a worklist of `[lo, hi)` index ranges into
+/// `remote`, initially just the whole slice. Each range is read with a
+/// single vectored call: if it comes back whole, every iovec in the range is
+/// done in that one syscall (the common case). Otherwise the iovec
+/// responsible for the short read is isolated and recovered on its own via
+/// [`recover_prefix`], and the rest of the range is pushed back onto the
+/// worklist to be retried as a single call again, rather than degrading to
+/// one syscall per iovec.
+fn read_vecs_capped(pid: Pid, remote: &[IoVec]) -> Vec<Vec<u8>> {
+    use core::ops::Range;
 
     // Allocate local buffers matching each remote region
     let mut backing_vecs: Vec<Vec<u8>> = remote.iter()
@@ -84,76 +125,209 @@ pub fn read_vecs(pid: Pid, remote: &[IoVec]) -> Vec<Option<Vec<u8>>> {
         .map(|(ptr, cap)| IoVec::new(ptr as u64, cap.unwrap()))
         .collect();
 
-    // NOTE: If the first remote iovec is invalid, `process_vm_readv` returns
-    // `EFAULT` immediately. If a later one is invalid, it returns the number
-    // of bytes read so far. We retry until all regions are processed.
+    // NOTE: If the first remote iovec of a range is invalid, `process_vm_readv`
+    // returns `EFAULT` immediately. If a later one is invalid, it returns the
+    // number of bytes read so far. Either way we retry the unresolved tail.
+    let mut worklist: Vec<Range<usize>> = vec![0..remote.len()];
 
-    // Get the total bytes that have yet to be read
-    let mut to_read: usize = backing_vecs.iter().map(Vec::capacity).sum();
+    while let Some(Range { start: lo, end: hi }) = worklist.pop() {
+        if lo >= hi { continue; }
 
-    // Index to track valid iovectors
-    let mut current_idx = 0;
+        let expected: usize = remote[lo..hi].iter().map(|v| v.len.get()).sum();
 
-    'read: loop {
-        // Attempt to read the memory into the local buffers
         let just_read: isize = unsafe {
             process_vm_readv(
+                pid,
+                local[lo..hi].as_ptr(), hi - lo,
+                remote[lo..hi].as_ptr(), hi - lo,
+                0,
+            )
+        };
+
+        // The whole range came back in one go
+        if just_read >= 0 && just_read as usize == expected {
+            for idx in lo..hi {
+                unsafe { backing_vecs[idx].set_len(backing_vecs[idx].capacity()); }
+            }
+            continue;
+        }
+
+        // The leading iovec of this range is entirely invalid: there's no
+        // partial byte count to work with, so recover it by bisection on
+        // its own and retry the rest of the range as a fresh call
+        if just_read < 0 {
+            backing_vecs[lo] = recover_prefix(pid, remote[lo].base, remote[lo].len.get());
+            worklist.push(lo + 1..hi);
+            continue;
+        }
+
+        // Short read: walk capacities to find how many leading iovecs were
+        // fully satisfied, then the next one is the suspect boundary
+        let mut just_read = just_read as usize;
+        let mut boundary = lo;
+
+        while boundary < hi && just_read >= remote[boundary].len.get() {
+            let cap = remote[boundary].len.get();
+            unsafe { backing_vecs[boundary].set_len(cap); }
+            just_read -= cap;
+            boundary += 1;
+        }
+
+        // `boundary == hi` would mean the full range was read, which is
+        // already handled above; it can't happen here
+        unsafe { backing_vecs[boundary].set_len(just_read); }
+        let rest = recover_prefix(pid,
+            remote[boundary].base + just_read as u64,
+            remote[boundary].len.get() - just_read);
+        backing_vecs[boundary].extend(rest);
+
+        worklist.push(boundary + 1..hi);
+    }
+
+    backing_vecs
+}
+
+/// Recovers the longest contiguous prefix of `len` bytes at `addr` that can
+/// actually be read, bisecting the range down to [`PAGE_SIZE`] granularity
+/// when the whole range can't be read in one go.
+///
+/// Returns however many bytes were recovered (possibly none, possibly the
+/// full range).
+fn recover_prefix(pid: Pid, addr: u64, len: usize) -> Vec<u8> {
+    let Some(nz_len) = NonZero::new(len) else { return Vec::new(); };
+
+    if let Some(data) = read(pid, addr, nz_len) {
+        return data;
+    }
+
+    // Nothing more to bisect; this page just isn't readable
+    if len as u64 <= PAGE_SIZE {
+        return Vec::new();
+    }
+
+    // Recover as much of the first half as we can. Only try to extend into
+    // the second half if the first half came back whole: a partial first
+    // half means the readable prefix already ends somewhere inside it
+    let half = (len as u64 / 2) as usize;
+    let mut recovered = recover_prefix(pid, addr, half);
+    if recovered.len() == half {
+        recovered.extend(recover_prefix(pid, addr + half as u64, len - half));
+    }
+
+    recovered
+}
+
+/// Writes each `data` slice in `remote` to its paired remote address.
+///
+/// `remote` is internally split into back-to-back batches of at most
+/// [`crate::IOV_MAX`] pairs, so callers don't need to track the kernel limit
+/// themselves; the per-pair results of each batch are concatenated in order,
+/// mirroring [`read_vecs`].
+///
+/// Will panic if no pairs are provided.
+pub fn write_vecs(pid: Pid, remote: &[(u64, &[u8])]) -> Vec<bool> {
+    assert!(remote.len() > 0);
+
+    remote.chunks(*crate::IOV_MAX.get().unwrap())
+        .flat_map(|batch| write_vecs_capped(pid, batch))
+        .collect()
+}
+
+/// Writes a single batch of at most [`crate::IOV_MAX`] `remote` pairs, as a
+/// sequence of `process_vm_writev()` calls. See [`write_vecs`].
+///
+/// Each `(addr, data)` pair maps 1:1 to a local iovec pointing directly at
+/// `data`.
+///
+/// If a region is invalid or only partly written, it's skipped, and the
+/// function retries with the remaining regions. Unlike [`read_vecs_capped`],
+/// a short region is not bisected to recover part of it.
+///
+/// Returns, for each pair, whether the write fully succeeded.
+///
+/// Will panic if more than [`crate::IOV_MAX`] pairs are provided.
+fn write_vecs_capped(pid: Pid, remote: &[(u64, &[u8])]) -> Vec<bool> {
+    assert!(*crate::IOV_MAX.get().unwrap() >= remote.len());
+
+    // Create the remote iovecs for the target addresses
+    let remote_vecs: Vec<IoVec> = remote.iter()
+        .map(|&(addr, data)| IoVec::new(addr, NonZero::new(data.len()).unwrap()))
+        .collect();
+
+    // Create the local iovecs pointing directly at the caller's buffers
+    let local: Vec<IoVec> = remote.iter()
+        .map(|&(_, data)| IoVec::new(data.as_ptr() as u64, NonZero::new(data.len()).unwrap()))
+        .collect();
+
+    // Tracks whether each pair has been fully written
+    let mut done = vec![false; remote.len()];
+
+    // NOTE: Same EFAULT/partial-write semantics as `read_vecs` apply here.
+
+    // Get the total bytes that have yet to be written
+    let mut to_write: usize = remote_vecs.iter().map(|v| v.len.into()).sum();
+
+    // Index to track valid iovecs
+    let mut current_idx = 0;
+
+    'write: loop {
+        // Attempt to write the memory from the local buffers
+        let just_written: isize = unsafe {
+            process_vm_writev(
                 pid,
                 local[current_idx..].as_ptr(),
                 local.len() - current_idx,
-                remote[current_idx..].as_ptr(),
-                remote.len() - current_idx,
+                remote_vecs[current_idx..].as_ptr(),
+                remote_vecs.len() - current_idx,
                 0,
             )
         };
 
         // If the first iovec is invalid, skip it
-        if just_read < 0 {
-            to_read -= backing_vecs[current_idx].capacity();
+        if just_written < 0 {
+            to_write -= remote_vecs[current_idx].len.into();
             current_idx += 1;
 
-            // If this iovec is also the last, stop, otherwise continue reading
-            if current_idx == remote.len() { break; } else { continue; }
+            // If this iovec is also the last, stop, otherwise continue writing
+            if current_idx == remote_vecs.len() { break; } else { continue; }
         }
 
-        // Cast just_read to usize as this is now guaranteed positive due to the
-        // check above
-        let mut just_read = just_read as usize;
+        // Cast just_written to usize as this is now guaranteed positive due to
+        // the check above
+        let mut just_written = just_written as usize;
 
-        // We got a read!
-        for vec in backing_vecs[current_idx..].iter_mut() {
-            // Take note of how many more bytes we have to read
-            let cap = vec.capacity();
-            to_read -= cap;
+        // We got a write!
+        for idx in current_idx..remote_vecs.len() {
+            // Take note of how many more bytes we have to write
+            let cap: usize = remote_vecs[idx].len.into();
+            to_write -= cap;
 
             // Update the current index to the iovecs for the next call
-            current_idx += 1;
+            current_idx = idx + 1;
 
-            // If there's no more bytes to read, this is the last iovec
-            if to_read == 0 {
-                // If we read enough to fill it, set its length. Otherwise this
-                // is an incomplete read so the iovec is invalid and skipped
-                if just_read == cap { unsafe { vec.set_len(cap); } }
-                break 'read;
+            // If there's no more bytes to write, this is the last iovec
+            if to_write == 0 {
+                // If we wrote enough to fill it, mark it done. Otherwise this
+                // is an incomplete write so the iovec is skipped
+                if just_written == cap { done[idx] = true; }
+                break 'write;
             }
 
-            // There's still more shit to read
+            // There's still more to write
 
-            // If we read enough to fill this vector, mark it as such; go next
-            if just_read >= cap {
-                unsafe { vec.set_len(cap); }
-                just_read -= cap;
+            // If we wrote enough to satisfy this vector, mark it done; go next
+            if just_written >= cap {
+                done[idx] = true;
+                just_written -= cap;
                 continue;
             }
 
-            // This iovec caused an incomplete read. `current_idx` already
-            // points past it, so it will be skipped on the next call
+            // This iovec caused an incomplete write. `current_idx` already
+            // points past it, so it is skipped and the next attempt retries
+            // the iovecs after it
             break;
         }
     }
 
-    // Get rid of partially read vectors
-    backing_vecs.into_iter()
-        .map(|v| (!v.is_empty()).then_some(v))
-        .collect()
+    done
 }
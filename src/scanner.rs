@@ -5,15 +5,50 @@
 use std::sync::OnceLock;
 use crate::Pid;
 
-/// The amount of memory to read in a single go when scanning
+/// The largest a single read window is ever allowed to be, regardless of
+/// [`Scanner::read_budget`]
 pub const CHUNK_SIZE: usize = 1024 * 1024 * 1024;
 
+/// The default per-scan read budget, well under [`CHUNK_SIZE`] so peak RSS
+/// stays predictable when targeting a process with multi-gigabyte mappings
+/// unless a caller explicitly asks for a bigger window
+pub const DEFAULT_READ_BUDGET: usize = 64 * 1024 * 1024;
+
 /// The maximum number of iovecs the `process_vm_readv()` syscall can handle
 pub static IOV_MAX: OnceLock<usize> = OnceLock::new();
 
+/// `RLIMIT_NOFILE`, as defined by the Linux kernel
+const RLIMIT_NOFILE: i32 = 7;
+
+/// Mirrors the kernel's `struct rlimit`
+#[repr(C)]
+struct Rlimit {
+    rlim_cur: u64,
+    rlim_max: u64,
+}
+
 unsafe extern "C" {
     /// The raw `sysconf()` syscall
     pub fn sysconf(name: i32) -> isize;
+
+    /// The raw `getrlimit()` syscall
+    fn getrlimit(resource: i32, rlim: *mut Rlimit) -> i32;
+
+    /// The raw `setrlimit()` syscall
+    fn setrlimit(resource: i32, rlim: *const Rlimit) -> i32;
+}
+
+/// Raises the soft `RLIMIT_NOFILE` limit up to the hard limit, the way
+/// tooling that spawns many concurrent I/O operations does. Best-effort:
+/// failures are silently ignored, since we fall back to whatever limit was
+/// already in place.
+fn raise_nofile_limit() {
+    let mut limit = Rlimit { rlim_cur: 0, rlim_max: 0 };
+
+    if unsafe { getrlimit(RLIMIT_NOFILE, &mut limit) } == 0 {
+        limit.rlim_cur = limit.rlim_max;
+        unsafe { setrlimit(RLIMIT_NOFILE, &limit) };
+    }
 }
 
 #[derive(Debug)]
@@ -21,8 +56,25 @@ pub struct Scanner {
     /// The PID we want to scan
     pid: Pid,
 
-    /// History of results
-    pub results: Vec<Vec<u64>>,
+    /// History of results, as `(address, value)` pairs. `value` is whatever
+    /// raw `u64` was read at that address when the pass that produced this
+    /// entry ran, used by [`Scanner::refine`](crate::Scanner::refine) to
+    /// compare against a later re-read without a separate typed rescan.
+    pub results: Vec<Vec<(u64, u64)>>,
+
+    /// `(address, last_value)` pairs from the most recent `s*`/`u*` scan,
+    /// kept around so a rescan can narrow the set in place without
+    /// re-walking the whole address space
+    pub value_matches: Option<Vec<(u64, crate::num::Value)>>,
+
+    /// Cached view of the target's readable memory, captured lazily and
+    /// reused for the duration of a command. See [`crate::Snapshot`].
+    pub snapshot: Option<crate::Snapshot>,
+
+    /// Largest buffer a single `process_vm_readv()` call is allowed to
+    /// allocate. Regions bigger than this are walked in windows of this
+    /// size instead of being read in one go. See [`Scanner::with_read_budget`].
+    read_budget: usize,
 }
 
 impl Scanner {
@@ -35,13 +87,59 @@ impl Scanner {
 
             let val = unsafe { sysconf(_SC_IOV_MAX) };
             let _ = IOV_MAX.set(usize::try_from(val).unwrap());
+
+            raise_nofile_limit();
         }
 
-        Self { pid, results: Vec::new(), }
+        Self {
+            pid,
+            results: Vec::new(),
+            value_matches: None,
+            snapshot: None,
+            read_budget: DEFAULT_READ_BUDGET,
+        }
+    }
+
+    /// Overrides the per-scan read budget, clamped to [`CHUNK_SIZE`]
+    ///
+    /// Following the same size-capping idea cargo uses to guard against
+    /// oversized unpacked crates, this bounds the buffer actually allocated
+    /// per `process_vm_readv()` call, so peak RSS stays predictable even
+    /// against processes with multi-gigabyte mappings.
+    pub fn with_read_budget(mut self, bytes: usize) -> Self {
+        self.read_budget = bytes.min(CHUNK_SIZE);
+        self
+    }
+
+    /// The configured per-scan read budget. See [`Scanner::with_read_budget`].
+    pub fn read_budget(&self) -> usize {
+        self.read_budget
     }
 
     /// Get the PID of the scanned process
     pub fn pid(&self) -> Pid {
         self.pid
     }
+
+    /// Enumerates this process's scannable memory regions
+    pub fn regions(&self) -> crate::Result<Vec<crate::proc_maps::Region>> {
+        Ok(crate::Maps::interesting_regions(self.pid)?.0)
+    }
+
+    /// Returns the cached memory snapshot, capturing one first if there
+    /// isn't one yet
+    pub fn snapshot(&mut self) -> crate::Result<&crate::Snapshot> {
+        if self.snapshot.is_none() {
+            self.snapshot = Some(crate::Snapshot::capture(self.pid, self.read_budget)?);
+        }
+
+        Ok(self.snapshot.as_ref().unwrap())
+    }
+
+    /// Drops the cached memory snapshot, so the next [`Scanner::snapshot`]
+    /// call recaptures it. Called between user commands so stale data is
+    /// never shown.
+    pub fn invalidate_snapshot(&mut self) {
+        self.snapshot = None;
+    }
 }
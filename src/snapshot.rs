@@ -0,0 +1,87 @@
+//! A cached, point-in-time view of a process's readable memory
+//!
+//! Built once via vectored reads over every readable region, one per
+//! read-budget-sized batch of regions, then
+//! queried locally so callers like the display command don't hit the kernel
+//! again for every value they decode.
+
+use crate::{Pid, Maps};
+
+/// A single readable region captured into the snapshot
+#[derive(Debug)]
+struct Chunk {
+    /// Start address of this region
+    base: u64,
+
+    /// The bytes actually read from it (may be a partial prefix, see
+    /// [`crate::remote::read_vecs`])
+    bytes: Vec<u8>,
+}
+
+/// A cached snapshot of a process's readable memory
+///
+/// The captured byte ranges double as the readability map: an address falls
+/// within one of them if and only if it was actually read. Stale as soon as
+/// the target's memory changes, so it should be [`Snapshot::refresh`]ed
+/// rather than held on to across commands.
+#[derive(Debug)]
+pub struct Snapshot {
+    /// The process this snapshot was captured from
+    pid: Pid,
+
+    /// The per-`process_vm_readv()`-call read budget this snapshot was
+    /// captured with, kept around so [`Snapshot::refresh`] reuses it
+    read_budget: usize,
+
+    /// The readable regions captured at [`Snapshot::capture`] time
+    chunks: Vec<Chunk>,
+}
+
+impl Snapshot {
+    /// Captures a fresh snapshot of every readable region of `pid`, reading
+    /// it in `read_budget`-sized windows
+    pub fn capture(pid: Pid, read_budget: usize) -> crate::Result<Self> {
+        let maps = Maps::r_regions(pid)?;
+        let iovecs = maps.chunks(0..u64::MAX, read_budget);
+
+        let chunks = iovecs
+            .flat_map(|batch| {
+                let memory = crate::remote::read_vecs(pid, &batch);
+                batch.into_iter().zip(memory)
+                    .filter(|(_, bytes)| !bytes.is_empty())
+                    .map(|(iovec, bytes)| Chunk { base: iovec.base, bytes })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Ok(Self { pid, read_budget, chunks })
+    }
+
+    /// Re-captures the snapshot from scratch, discarding whatever was
+    /// cached before
+    pub fn refresh(&mut self) -> crate::Result<()> {
+        *self = Self::capture(self.pid, self.read_budget)?;
+        Ok(())
+    }
+
+    /// Finds the cached chunk covering `addr`, if any
+    fn chunk_at(&self, addr: u64) -> Option<&Chunk> {
+        self.chunks.iter()
+            .find(|c| addr >= c.base && addr < c.base + c.bytes.len() as u64)
+    }
+
+    /// Checks whether `addr` falls within memory the snapshot actually read
+    pub fn is_readable(&self, addr: u64) -> bool {
+        self.chunk_at(addr).is_some()
+    }
+
+    /// Returns the `len` bytes at `addr`, as long as the snapshot fully
+    /// covers them. Returns `None` for ranges it doesn't cover (spanning a
+    /// hole, or never captured); it is up to the caller to fall back to a
+    /// direct read in that case.
+    pub fn slice(&self, addr: u64, len: usize) -> Option<&[u8]> {
+        let chunk = self.chunk_at(addr)?;
+        let offset = (addr - chunk.base) as usize;
+        chunk.bytes.get(offset..offset + len)
+    }
+}
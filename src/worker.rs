@@ -0,0 +1,191 @@
+//! Crash-isolated scanning via a re-exec'd worker subprocess
+//!
+//! `process_vm_readv()` can fault or wedge if the target unmaps memory out
+//! from under a scan, and a panic in the scan loop would otherwise take the
+//! whole session down with it. Rather than forking, the actual scan runs in
+//! a re-exec of this same binary with a hidden [`WORKER_FLAG`] subcommand
+//! that owns a `Scanner` for one pid and streams results back over its
+//! stdout as they're found. The parent supervises the child, keeps whatever
+//! partial results arrived, and reports the region that was in flight if
+//! the child dies instead of losing the whole scan.
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use crate::{Pid, Maps};
+use crate::num::Value;
+use crate::proc_maps::RegionKind;
+#[cfg(not(feature = "rayon"))]
+use crate::commands::{scan_batch, ScanCarry};
+
+/// Hidden subcommand that switches the re-exec'd process into worker mode.
+/// Not advertised in the regular CLI usage text.
+pub const WORKER_FLAG: &str = "--scan-worker";
+
+/// Runs this process as a scan worker
+///
+/// Expects `std::env::args()` to be `<exe> --scan-worker <pid> <letter>
+/// <start> <end> [<constraint>...] [--heap|--stack|--anon|--file]
+/// [--budget=<bytes>]`, mirroring the `s*` commands' own arguments. Streams
+/// one `address,value` line per match to stdout, and one `# lo,hi` progress
+/// line before each batch, flushing after each so the parent sees results
+/// as they're produced rather than only at exit.
+pub fn run_worker() -> Result<(), String> {
+    let raw: Vec<String> = std::env::args().skip(2).collect();
+    let args: Vec<&str> = raw.iter().map(String::as_str).collect();
+
+    let pid = args.first()
+        .ok_or("worker: missing pid".to_string())
+        .and_then(|s| Pid::try_from(*s).map_err(|e| format!("worker: invalid pid: {:?}", e)))?;
+
+    let letter = args.get(1)
+        .and_then(|s| s.chars().next())
+        .ok_or("worker: missing value letter".to_string())?;
+    let value = Value::default_from_letter(letter);
+
+    let start = args.get(2)
+        .ok_or("worker: missing start address".to_string())
+        .and_then(|s| crate::num::parse::<u64>(s).map_err(|e| format!("{:?}", e)))?;
+    let end = args.get(3)
+        .ok_or("worker: missing end address".to_string())
+        .and_then(|s| crate::num::parse::<u64>(s).map_err(|e| format!("{:?}", e)))?;
+    let end = if end == 0 { u64::MAX } else { end };
+
+    let rest = args.get(4..).unwrap_or(&[]);
+
+    // A trailing `--budget=N` forwards the scanner's read budget, since the
+    // worker doesn't have the `Scanner` that owns it
+    let (budget, rest) = match rest.last().and_then(|tok| tok.strip_prefix("--budget=")) {
+        Some(n) => {
+            let budget = crate::num::parse::<usize>(n)
+                .map_err(|e| format!("worker: invalid read budget: {:?}", e))?;
+            (budget, &rest[..rest.len() - 1])
+        }
+        None => (crate::DEFAULT_READ_BUDGET, rest),
+    };
+
+    let (kind, constraint_args) = match rest.last() {
+        Some(tok) if tok.starts_with("--") => (RegionKind::from_flag(tok), &rest[..rest.len() - 1]),
+        _ => (None, rest),
+    };
+
+    let constraints = crate::commands::parse_constraints(constraint_args, value)?;
+
+    let maps = match kind {
+        Some(kind) => Maps::regions_of_kind(pid, kind),
+        None       => Maps::interesting_regions(pid),
+    }.map_err(|e| format!("worker: couldn't parse memory map: {:?}", e))?;
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+
+    // With `rayon`, hand the whole range to the work-stealing scanner in one
+    // go; that's faster but the parent only sees results once we're done.
+    // Without it, walk one batch at a time so a progress marker can be
+    // flushed before each read, giving the parent something to report if we
+    // die partway through.
+    #[cfg(feature = "rayon")]
+    {
+        let _ = writeln!(out, "# {start:x},{end:x}");
+        let _ = out.flush();
+
+        let matches = crate::parallel::scan_parallel(pid, &maps, start..end, value, &constraints, budget);
+        for (addr, val) in matches {
+            let _ = writeln!(out, "{addr:x},{:x}", val.as_u64());
+        }
+        let _ = out.flush();
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    let mut carry = ScanCarry::default();
+
+    #[cfg(not(feature = "rayon"))]
+    for batch in maps.chunks(start..end, budget) {
+        let lo = batch.first().map(|i| i.base).unwrap_or(0);
+        let hi = batch.last().map(|i| i.base + i.len.get() as u64).unwrap_or(0);
+        let _ = writeln!(out, "# {lo:x},{hi:x}");
+        let _ = out.flush();
+
+        let mut matches = Vec::new();
+        scan_batch(pid, &mut matches, &batch, value, &constraints, &mut carry);
+
+        for (addr, val) in matches {
+            let _ = writeln!(out, "{addr:x},{:x}", val.as_u64());
+        }
+        let _ = out.flush();
+    }
+
+    Ok(())
+}
+
+/// Spawns a worker subprocess to scan `pid` for slots within `start..end`
+/// of `letter`'s type satisfying `constraint_args`, restricted to `kind`'s
+/// regions if given, collecting results as they stream back.
+///
+/// If the worker dies before finishing (crash, signal, or it got killed
+/// after wedging), whatever matches had already streamed back are still
+/// returned, and the region that was in flight at the time is reported
+/// rather than the scan silently coming up empty.
+pub fn supervised_scan(
+    pid: Pid,
+    letter: char,
+    start: u64,
+    end: u64,
+    constraint_args: &[&str],
+    kind: Option<RegionKind>,
+    value: Value,
+    read_budget: usize,
+) -> Result<Vec<(u64, Value)>, String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Couldn't locate our own executable: {:?}", e))?;
+
+    let mut child = Command::new(exe)
+        .arg(WORKER_FLAG)
+        .arg(pid.0.get().to_string())
+        .arg(letter.to_string())
+        .arg(start.to_string())
+        .arg(end.to_string())
+        .args(constraint_args)
+        .args(kind.and_then(|k| k.as_flag()))
+        .arg(format!("--budget={read_budget}"))
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Couldn't spawn scan worker: {:?}", e))?;
+
+    let stdout = child.stdout.take().ok_or("Couldn't capture scan worker's stdout".to_string())?;
+
+    let mut matches = Vec::new();
+    let mut last_region: Option<(u64, u64)> = None;
+
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break; };
+
+        if let Some(rest) = line.strip_prefix("# ") {
+            if let Some((lo, hi)) = rest.split_once(',') {
+                if let (Ok(lo), Ok(hi)) = (u64::from_str_radix(lo, 16), u64::from_str_radix(hi, 16)) {
+                    last_region = Some((lo, hi));
+                }
+            }
+            continue;
+        }
+
+        let Some((addr, bits)) = line.split_once(',') else { continue; };
+        let (Ok(addr), Ok(bits)) = (u64::from_str_radix(addr, 16), u64::from_str_radix(bits, 16))
+            else { continue; };
+
+        let mut found = value;
+        found.from_le_bytes(&bits.to_le_bytes()[..found.bytes()]);
+        matches.push((addr, found));
+    }
+
+    let status = child.wait().map_err(|e| format!("Couldn't wait on scan worker: {:?}", e))?;
+
+    if !status.success() {
+        let where_ = last_region
+            .map(|(lo, hi)| format!(" while scanning 0x{lo:x}..0x{hi:x}"))
+            .unwrap_or_default();
+        println!("Scan worker exited abnormally ({status}){where_}; \
+            kept {} match(es) collected before it died", matches.len());
+    }
+
+    Ok(matches)
+}